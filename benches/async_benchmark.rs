@@ -1,10 +1,17 @@
 use std::time::Duration;
 
 use create3::{
-    generate_salt, generate_salt_multithread, generate_salt_prefix,
-    generate_salt_prefix_multithread,
+    calc_addr_batch, calc_addr_with_bytes, generate_salt,
+    generate_salt_multithread, generate_salt_prefix,
+    generate_salt_prefix_multithread, score::mine_most_zeros, Create3,
 };
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{
+    black_box, criterion_group, criterion_main, BenchmarkId, Criterion,
+    Throughput,
+};
+
+// salts tried per iteration of the throughput benches.
+const THROUGHPUT_ATTEMPTS: u64 = 10_000;
 
 /// bench single-threaded generate salt.
 fn generate_salt_benchmark(c: &mut Criterion) {
@@ -54,6 +61,96 @@ fn generate_salt_prefix_multithread_benchmark(c: &mut Criterion) {
     });
 }
 
+/// bench the latency of a single address calculation.
+fn calc_addr_with_bytes_benchmark(c: &mut Criterion) {
+    let deployer = black_box(
+        hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap(),
+    );
+    let salt = black_box([0x42u8; 32]);
+    c.bench_function("calc addr with bytes", |b| {
+        b.iter(|| calc_addr_with_bytes(&deployer, &salt))
+    });
+}
+
+/// bench single-threaded attempts per second; an empty prefix matches on
+/// every attempt, so each match is exactly one attempt.
+fn single_thread_throughput_benchmark(c: &mut Criterion) {
+    let deployer: [u8; 20] =
+        hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let create3 = Create3::new(&deployer);
+    let mut group = c.benchmark_group("single-threaded attempts");
+    group.throughput(Throughput::Elements(THROUGHPUT_ATTEMPTS));
+    group.bench_function("empty prefix", |b| {
+        b.iter(|| {
+            create3
+                .iter_matches("")
+                .unwrap()
+                .take(THROUGHPUT_ATTEMPTS as usize)
+                .count()
+        })
+    });
+    group.finish();
+}
+
+/// bench how attempts per second scale with the thread count.
+fn multithread_scaling_benchmark(c: &mut Criterion) {
+    let deployer = black_box(
+        hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap(),
+    );
+    let mut group = c.benchmark_group("multi-threaded attempts");
+    group.throughput(Throughput::Elements(THROUGHPUT_ATTEMPTS));
+    for threads in [1, 2, 4] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(threads),
+            &threads,
+            |b, &threads| {
+                b.iter(|| {
+                    mine_most_zeros(&deployer, threads, THROUGHPUT_ATTEMPTS)
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// bench batch address calculation against single calls in a loop.
+fn calc_addr_batch_benchmark(c: &mut Criterion) {
+    let deployer = black_box(
+        hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap(),
+    );
+    let salts: Vec<[u8; 32]> = (0..1024u32)
+        .map(|i| {
+            let mut salt = [0u8; 32];
+            salt[28..].copy_from_slice(&i.to_be_bytes());
+            salt
+        })
+        .collect();
+    c.bench_function("calc addr batch of 1024", |b| {
+        b.iter(|| calc_addr_batch(&deployer, &salts))
+    });
+    c.bench_function("calc addr loop of 1024", |b| {
+        b.iter(|| {
+            salts
+                .iter()
+                .map(|salt| calc_addr_with_bytes(&deployer, salt))
+                .collect::<Vec<_>>()
+        })
+    });
+}
+
+criterion_group!(
+    name = calc_addr_benches;
+    config = Criterion::default();
+    targets = calc_addr_with_bytes_benchmark, calc_addr_batch_benchmark
+);
+criterion_group!(
+    name = throughput_benches;
+    config = Criterion::default();
+    targets = single_thread_throughput_benchmark, multithread_scaling_benchmark
+);
 criterion_group!(
     name = generate_salt_benches;
     config = Criterion::default()
@@ -61,4 +158,4 @@ criterion_group!(
     targets = generate_salt_benchmark, generate_salt_multithread_benchmark,
         generate_salt_prefix_benchmark, generate_salt_prefix_multithread_benchmark
 );
-criterion_main!(generate_salt_benches);
+criterion_main!(generate_salt_benches, calc_addr_benches, throughput_benches);
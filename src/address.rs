@@ -0,0 +1,207 @@
+use core::fmt::{self, Display};
+use core::str::FromStr;
+
+use crate::{
+    checksum::to_checksum_address, errors::Create3GenerateSaltError,
+    strip_hex_prefix,
+};
+
+/// a 20-byte ethereum address.
+///
+/// displays in eip-55 checksummed form and parses from '0x' followed by 40
+/// hex characters in any case.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Address(pub [u8; 20]);
+
+impl Address {
+    /// returns the raw address bytes.
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+}
+
+impl From<[u8; 20]> for Address {
+    fn from(bytes: [u8; 20]) -> Self {
+        Address(bytes)
+    }
+}
+
+impl Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&to_checksum_address(&self.0))
+    }
+}
+
+impl FromStr for Address {
+    type Err = Create3GenerateSaltError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s
+            .strip_prefix("0x")
+            .filter(|d| d.len() == 40)
+            .ok_or(Create3GenerateSaltError::InvalidAddress)?;
+        let mut bytes = [0u8; 20];
+        hex::decode_to_slice(digits, &mut bytes)
+            .map_err(|_| Create3GenerateSaltError::InvalidAddress)?;
+        Ok(Address(bytes))
+    }
+}
+
+/// a create3 deployer address.
+///
+/// parses from 40 hex characters, with or without a leading '0x' or '0X',
+/// which covers both pasted addresses and the bare form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Deployer(pub [u8; 20]);
+
+impl Deployer {
+    /// returns the raw address bytes.
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    /// parses a deployer like `from_str`, but also checks the eip-55
+    /// checksum when the input mixes upper and lowercase letters, so a typo
+    /// in a checksummed address is caught. all-lowercase and all-uppercase
+    /// input carries no checksum and is accepted as is.
+    pub fn parse_strict(s: &str) -> Result<Self, Create3GenerateSaltError> {
+        let deployer: Deployer = s.parse()?;
+        let digits = strip_hex_prefix(s);
+        let mixed_case = digits.bytes().any(|b| b.is_ascii_lowercase())
+            && digits.bytes().any(|b| b.is_ascii_uppercase());
+        let expected = to_checksum_address(&deployer.0);
+        if mixed_case && expected[2..] != *digits {
+            return Err(Create3GenerateSaltError::ChecksumMismatch {
+                expected,
+            });
+        }
+        Ok(deployer)
+    }
+}
+
+impl From<[u8; 20]> for Deployer {
+    fn from(bytes: [u8; 20]) -> Self {
+        Deployer(bytes)
+    }
+}
+
+impl Display for Deployer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&to_checksum_address(&self.0))
+    }
+}
+
+impl FromStr for Deployer {
+    type Err = Create3GenerateSaltError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = strip_hex_prefix(s);
+        if digits.len() != 40 {
+            return Err(Create3GenerateSaltError::InvalidDeployer);
+        }
+        let mut bytes = [0u8; 20];
+        hex::decode_to_slice(digits, &mut bytes)
+            .map_err(|_| Create3GenerateSaltError::InvalidDeployer)?;
+        Ok(Deployer(bytes))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{
+        address::{Address, Deployer},
+        calc_addr, calc_addr_typed,
+        errors::Create3GenerateSaltError,
+    };
+
+    #[test]
+    fn should_round_trip_checksummed_address() {
+        let s = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        let address: Address = s.parse().unwrap();
+        assert_eq!(address.to_string(), s);
+        assert_eq!(address.as_bytes()[0], 0x5a);
+        assert_eq!(s.to_lowercase().parse::<Address>(), Ok(address));
+    }
+
+    #[test]
+    fn should_error_on_invalid_address() {
+        for s in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAe",
+            "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg",
+        ] {
+            assert_eq!(
+                s.parse::<Address>(),
+                Err(Create3GenerateSaltError::InvalidAddress)
+            );
+        }
+    }
+
+    #[test]
+    fn calc_addr_typed_should_match_calc_addr() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        assert_eq!(
+            calc_addr_typed(&deployer, b"nacl").as_bytes(),
+            &calc_addr(&deployer, b"nacl")
+        );
+    }
+
+    #[test]
+    fn should_parse_deployer_with_or_without_0x() {
+        let bare = "5e17b14ADd6c386305A32928F985b29bbA34Eff5";
+        let deployer: Deployer = bare.parse().unwrap();
+        assert_eq!(deployer.as_bytes()[..], hex::decode(bare).unwrap()[..]);
+        assert_eq!(format!("0x{}", bare).parse(), Ok(deployer));
+        assert_eq!(format!("0X{}", bare).parse(), Ok(deployer));
+    }
+
+    #[test]
+    fn should_check_deployer_checksum_in_strict_mode() {
+        let checksummed = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        let deployer = Deployer::parse_strict(checksummed).unwrap();
+        assert_eq!(deployer.to_string(), checksummed);
+        assert_eq!(Deployer::parse_strict(&checksummed[2..]), Ok(deployer));
+        assert_eq!(
+            Deployer::parse_strict(&format!("0X{}", &checksummed[2..])),
+            Ok(deployer)
+        );
+        assert_eq!(
+            Deployer::parse_strict(&checksummed.to_lowercase()),
+            Ok(deployer)
+        );
+        assert_eq!(
+            Deployer::parse_strict(&checksummed[2..].to_uppercase()),
+            Ok(deployer)
+        );
+
+        // one letter with the wrong case.
+        let corrupted = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+        assert_eq!(corrupted.parse(), Ok(deployer));
+        assert_eq!(
+            Deployer::parse_strict(corrupted),
+            Err(Create3GenerateSaltError::ChecksumMismatch {
+                expected: checksummed.to_owned()
+            })
+        );
+        assert_eq!(
+            Deployer::parse_strict("0x12"),
+            Err(Create3GenerateSaltError::InvalidDeployer)
+        );
+    }
+
+    #[test]
+    fn should_error_on_invalid_deployer() {
+        for s in [
+            "5e17b14ADd6c386305A32928F985b29bbA34Eff",
+            "0x5e17b14ADd6c386305A32928F985b29bbA34Ef",
+            "5e17b14ADd6c386305A32928F985b29bbA34Effz",
+            "0x0x5e17b14ADd6c386305A32928F985b29bbA34E",
+        ] {
+            assert_eq!(
+                s.parse::<Deployer>(),
+                Err(Create3GenerateSaltError::InvalidDeployer)
+            );
+        }
+    }
+}
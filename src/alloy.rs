@@ -0,0 +1,63 @@
+use alloy_primitives::{Address, B256};
+
+use crate::{calc_addr_with_bytes, SaltResult};
+
+/// calculates the address of a contract deployed through a deployer that
+/// uses the create3 pattern, using alloy types.
+///
+/// arguments:
+/// - deployer: create3 deployer address.
+/// - salt: 32-byte salt passed to the deployer.
+///
+/// returns: the address of the contract.
+pub fn calc_addr_alloy(deployer: Address, salt: B256) -> Address {
+    Address::from(calc_addr_with_bytes(deployer.as_ref(), &salt.0))
+}
+
+/// converts a generated salt into its salt hash and address, ready to pass
+/// to alloy-based deploy code.
+impl From<SaltResult> for (B256, Address) {
+    fn from(result: SaltResult) -> Self {
+        (B256::from(result.salt_hash), Address::from(result.address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::borrow::ToOwned;
+
+    use alloy_primitives::{Address, B256};
+
+    use crate::{alloy::calc_addr_alloy, calc_addr_with_bytes, SaltResult};
+
+    #[test]
+    fn calc_addr_alloy_should_match_calc_addr_with_bytes() {
+        let deployer: [u8; 20] =
+            hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let salt: [u8; 32] = hex::decode(
+            "3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb",
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        let address =
+            calc_addr_alloy(Address::from(deployer), B256::from(salt));
+        assert_eq!(address.0 .0, calc_addr_with_bytes(&deployer, &salt));
+    }
+
+    #[test]
+    fn should_convert_salt_result_to_alloy_types() {
+        let result = SaltResult {
+            salt: "nacl".to_owned(),
+            salt_hash: [0x11; 32],
+            address: [0x22; 20],
+            attempts: 1,
+        };
+        let (salt_hash, address): (B256, Address) = result.into();
+        assert_eq!(salt_hash, B256::repeat_byte(0x11));
+        assert_eq!(address, Address::repeat_byte(0x22));
+    }
+}
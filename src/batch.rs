@@ -0,0 +1,186 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::stream::parse_ndjson_address;
+
+/// progress of a batch run, persisted after every result so an interrupted
+/// batch can pick up where it stopped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchProgress {
+    /// desired address prefix, as entered.
+    pub prefix: String,
+    /// create3 deployer address (bytes).
+    pub deployer: Vec<u8>,
+    /// number of results already written.
+    pub completed_count: u64,
+    /// file the results are appended to.
+    pub results_path: PathBuf,
+}
+
+impl BatchProgress {
+    /// starts tracking a new batch with no completed results.
+    pub fn new(deployer: &[u8], prefix: &str, results_path: &Path) -> Self {
+        BatchProgress {
+            prefix: prefix.to_owned(),
+            deployer: deployer.to_owned(),
+            completed_count: 0,
+            results_path: results_path.to_owned(),
+        }
+    }
+
+    /// checks whether a saved batch was started with the same parameters,
+    /// so resuming it won't mix results from incompatible runs.
+    pub fn is_compatible(&self, other: &BatchProgress) -> bool {
+        self.deployer == other.deployer
+            && self.prefix.to_lowercase() == other.prefix.to_lowercase()
+            && self.results_path == other.results_path
+    }
+
+    /// reads batch progress from a file.
+    ///
+    /// returns: the saved progress, or None if there is no progress file.
+    pub fn load(path: &Path) -> io::Result<Option<Self>> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let invalid = |field: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("progress file has a missing or bad {}.", field),
+            )
+        };
+        let field = |key: &str| {
+            contents.lines().find_map(|line| {
+                line.strip_prefix(key)?.strip_prefix('=').map(str::to_owned)
+            })
+        };
+        Ok(Some(BatchProgress {
+            prefix: field("prefix").ok_or_else(|| invalid("prefix"))?,
+            deployer: field("deployer")
+                .and_then(|d| hex::decode(d).ok())
+                .ok_or_else(|| invalid("deployer"))?,
+            completed_count: field("completed_count")
+                .and_then(|c| c.parse().ok())
+                .ok_or_else(|| invalid("completed_count"))?,
+            results_path: field("results_path")
+                .map(PathBuf::from)
+                .ok_or_else(|| invalid("results_path"))?,
+        }))
+    }
+
+    /// writes batch progress to a file.
+    ///
+    /// the file is replaced in one step, so a crash mid-write leaves the
+    /// previous progress intact.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let tmp = path.with_extension("tmp");
+        fs::write(
+            &tmp,
+            format!(
+                "prefix={}\ndeployer={}\ncompleted_count={}\nresults_path={}\n",
+                self.prefix,
+                hex::encode(&self.deployer),
+                self.completed_count,
+                self.results_path.display()
+            ),
+        )?;
+        fs::rename(tmp, path)
+    }
+
+    /// reads the addresses of the results already written to the results
+    /// file, so a resumed batch of distinct addresses doesn't repeat them.
+    ///
+    /// lines that aren't results, like one cut short by a crash, are
+    /// skipped.
+    ///
+    /// returns: the addresses found, or an empty set if there is no results
+    /// file yet.
+    pub fn load_result_addresses(&self) -> io::Result<HashSet<[u8; 20]>> {
+        match fs::read_to_string(&self.results_path) {
+            Ok(contents) => {
+                Ok(contents.lines().filter_map(parse_ndjson_address).collect())
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(HashSet::new()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use crate::{batch::BatchProgress, stream::write_ndjson_line};
+
+    #[test]
+    fn should_reload_saved_progress() {
+        let dir = std::env::temp_dir()
+            .join(format!("create3-batch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("batch.progress");
+        assert_eq!(BatchProgress::load(&path).unwrap(), None);
+
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let mut progress =
+            BatchProgress::new(&deployer, "abc", Path::new("results.ndjson"));
+        progress.completed_count = 42;
+        progress.save(&path).unwrap();
+        assert_eq!(BatchProgress::load(&path).unwrap(), Some(progress));
+
+        fs::write(&path, "prefix=abc\ncompleted_count=x\n").unwrap();
+        assert!(BatchProgress::load(&path).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn should_load_addresses_of_written_results() {
+        let dir = std::env::temp_dir()
+            .join(format!("create3-batch-results-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let results = dir.join("results.ndjson");
+        let progress = BatchProgress::new(&[0x11; 20], "ab", &results);
+        assert!(progress.load_result_addresses().unwrap().is_empty());
+
+        let mut file = fs::File::create(&results).unwrap();
+        write_ndjson_line(&mut file, &[0xab; 20], "one", &[0; 32]).unwrap();
+        write_ndjson_line(&mut file, &[0xcd; 20], "two", &[1; 32]).unwrap();
+        // a line torn by a crash mid-write.
+        std::io::Write::write_all(&mut file, b"{\"address\":\"0xab").unwrap();
+        drop(file);
+        let addresses = progress.load_result_addresses().unwrap();
+        assert_eq!(addresses.len(), 2);
+        assert!(addresses.contains(&[0xab; 20]));
+        assert!(addresses.contains(&[0xcd; 20]));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn should_only_resume_compatible_batches() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let results = Path::new("results.ndjson");
+        let mut saved = BatchProgress::new(&deployer, "abc", results);
+        saved.completed_count = 7;
+        assert!(
+            saved.is_compatible(&BatchProgress::new(&deployer, "ABC", results))
+        );
+        assert!(!saved
+            .is_compatible(&BatchProgress::new(&deployer, "abd", results)));
+        assert!(!saved.is_compatible(&BatchProgress::new(
+            &[0x11; 20],
+            "abc",
+            results
+        )));
+        assert!(!saved.is_compatible(&BatchProgress::new(
+            &deployer,
+            "abc",
+            Path::new("other.ndjson")
+        )));
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use crate::{
+    calc_addr_with_bytes, errors::Create3GenerateSaltError, generate_salt,
+};
+
+/// remembers the salt behind each generated address, so salts can be
+/// recovered later from just the address.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SaltCache {
+    entries: HashMap<[u8; 20], [u8; 32]>,
+}
+
+impl SaltCache {
+    /// creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// records the 32-byte salt that produced an address.
+    pub fn insert(&mut self, address: [u8; 20], salt: [u8; 32]) {
+        self.entries.insert(address, salt);
+    }
+
+    /// returns: the salt recorded for an address, if any.
+    pub fn get(&self, address: &[u8; 20]) -> Option<[u8; 32]> {
+        recover_salt_from_cache(address, &self.entries)
+    }
+
+    /// returns: every recorded address and salt.
+    pub fn entries(&self) -> &HashMap<[u8; 20], [u8; 32]> {
+        &self.entries
+    }
+
+    /// returns: the number of recorded addresses.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// returns: true if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// looks up the salt previously generated for an address.
+///
+/// salts cannot be derived from an address, so this only finds salts that
+/// were recorded when they were generated.
+///
+/// arguments:
+/// - address: 20-byte contract address.
+/// - cache: map of addresses to the 32-byte salts that produced them.
+///
+/// returns: the recorded 32-byte salt, if any.
+pub fn recover_salt_from_cache(
+    address: &[u8; 20],
+    cache: &HashMap<[u8; 20], [u8; 32]>,
+) -> Option<[u8; 32]> {
+    cache.get(address).copied()
+}
+
+/// generates a random salt for a given prefix and records it in a cache.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - cache: cache to record the address and salt in.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt).
+pub fn generate_salt_cached(
+    deployer: &[u8],
+    prefix: &str,
+    cache: &mut SaltCache,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    let (salt, salt_bytes) = generate_salt(deployer, prefix)?;
+    cache.insert(calc_addr_with_bytes(deployer, &salt_bytes), salt_bytes);
+    Ok((salt, salt_bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cache::{generate_salt_cached, recover_salt_from_cache, SaltCache},
+        calc_addr_with_bytes,
+    };
+
+    #[test]
+    fn should_recover_generated_salts() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let mut cache = SaltCache::new();
+        let mut generated = Vec::new();
+        for run in ["0", "ab", "Def"].iter() {
+            let (_, salt) =
+                generate_salt_cached(&deployer, run, &mut cache).unwrap();
+            generated.push((calc_addr_with_bytes(&deployer, &salt), salt));
+        }
+        assert_eq!(cache.len(), generated.len());
+        for (address, salt) in generated.iter() {
+            assert_eq!(cache.get(address), Some(*salt));
+            assert_eq!(
+                recover_salt_from_cache(address, cache.entries()),
+                Some(*salt)
+            );
+        }
+    }
+
+    #[test]
+    fn should_not_recover_unknown_address() {
+        let mut cache = SaltCache::new();
+        assert!(cache.is_empty());
+        cache.insert([0x11; 20], [0x22; 32]);
+        assert_eq!(cache.get(&[0x33; 20]), None);
+        assert_eq!(recover_salt_from_cache(&[0x33; 20], cache.entries()), None);
+    }
+}
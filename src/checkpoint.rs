@@ -0,0 +1,113 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{serde_hex, SaltResult};
+
+/// state of a long counter-based search, saved as json so mining for a hard
+/// prefix can be spread over several sessions.
+///
+/// pass `attempts_done` as the `start` of `generate_salt_counter` to pick up
+/// where the previous session stopped.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MiningCheckpoint {
+    /// create3 deployer address.
+    #[serde(with = "serde_hex")]
+    pub deployer: [u8; 20],
+    /// desired address prefix, as entered.
+    pub prefix: String,
+    /// number of counter values already tried.
+    pub attempts_done: u64,
+    /// best result found so far, if any.
+    pub best: Option<SaltResult>,
+}
+
+impl MiningCheckpoint {
+    /// starts a checkpoint for a new search with nothing tried yet.
+    pub fn new(deployer: [u8; 20], prefix: &str) -> Self {
+        MiningCheckpoint {
+            deployer,
+            prefix: prefix.to_owned(),
+            attempts_done: 0,
+            best: None,
+        }
+    }
+
+    /// reads a checkpoint from a json file.
+    ///
+    /// returns: the saved checkpoint, or None if there is no checkpoint file.
+    pub fn load_checkpoint(path: &Path) -> io::Result<Option<Self>> {
+        let contents = match fs::read(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        serde_json::from_slice(&contents)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// writes the checkpoint to a json file.
+    ///
+    /// the file is replaced in one step, so a crash mid-write leaves the
+    /// previous checkpoint intact.
+    pub fn save_checkpoint(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_vec(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(tmp, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{
+        calc_addr, checkpoint::MiningCheckpoint, generate_salt_counter,
+        SaltResult,
+    };
+
+    #[test]
+    fn should_reload_saved_checkpoint() {
+        let dir = std::env::temp_dir()
+            .join(format!("create3-checkpoint-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("mining.json");
+        assert_eq!(MiningCheckpoint::load_checkpoint(&path).unwrap(), None);
+
+        let deployer: [u8; 20] =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let mut checkpoint = MiningCheckpoint::new(deployer, "abc");
+        checkpoint.save_checkpoint(&path).unwrap();
+        assert_eq!(
+            MiningCheckpoint::load_checkpoint(&path).unwrap(),
+            Some(checkpoint.clone())
+        );
+
+        let (counter, salt_hash) =
+            generate_salt_counter(&deployer, "a", 0).unwrap();
+        checkpoint.attempts_done = counter + 1;
+        checkpoint.best = Some(SaltResult {
+            salt: "a\"b".to_owned(),
+            salt_hash,
+            address: calc_addr(&deployer, b"a\"b"),
+            attempts: counter + 1,
+        });
+        checkpoint.save_checkpoint(&path).unwrap();
+        assert_eq!(
+            MiningCheckpoint::load_checkpoint(&path).unwrap(),
+            Some(checkpoint)
+        );
+
+        fs::write(&path, "{\"prefix\":\"abc\"}").unwrap();
+        assert!(MiningCheckpoint::load_checkpoint(&path).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,308 @@
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+
+use sha3::{Digest, Keccak256};
+
+#[cfg(feature = "std")]
+use crate::{
+    calc_addr, check_deployer, errors::Create3GenerateSaltError, random_salt,
+    sanitize_checksum_prefix, search_rng, SaltResult,
+};
+
+/// converts a 20-byte ethereum address into its eip-55 checksummed form.
+/// the address is converted to lowercase hex, hashed with keccak256, and
+/// characters are uppercased based on the hash.
+pub fn to_checksum_address(address: &[u8]) -> String {
+    let address_hex = hex::encode(address);
+    let mut hasher = Keccak256::new();
+    hasher.update(address_hex.as_bytes());
+    let hash = hasher.finalize();
+    let hash_hex = hex::encode(hash);
+    let mut result = String::from("0x");
+    for (i, ch) in address_hex.chars().enumerate() {
+        let hash_digit = hash_hex.chars().nth(i).unwrap();
+        let hash_val = hash_digit.to_digit(16).unwrap();
+        if hash_val >= 8 {
+            result.push(ch.to_ascii_uppercase());
+        } else {
+            result.push(ch);
+        }
+    }
+    result
+}
+
+/// generates a salt whose checksummed address ends in uppercase letters.
+///
+/// the last `count` alphabetic characters of the eip-55 form of the address
+/// must all be uppercase; each one halves the odds, so the search takes
+/// about 2^count attempts on top of needing enough letters.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - count: number of trailing letters that must be uppercase (max 40).
+///
+/// returns: the matching salt and its address.
+#[cfg(feature = "std")]
+pub fn generate_salt_checksum_caps_suffix(
+    deployer: &[u8],
+    count: usize,
+) -> Result<SaltResult, Create3GenerateSaltError> {
+    if count > 40 {
+        return Err(Create3GenerateSaltError::CountTooLarge);
+    }
+    check_deployer(deployer)?;
+    let mut rng = search_rng();
+    let mut attempts: u64 = 0;
+    loop {
+        let salt = random_salt(&mut rng, 10);
+        let address = calc_addr(deployer, salt.as_bytes());
+        attempts += 1;
+        if has_caps_suffix(&to_checksum_address(&address), count) {
+            return Ok(SaltResult {
+                salt_hash: Keccak256::digest(&salt).into(),
+                salt,
+                address,
+                attempts,
+            });
+        }
+    }
+}
+
+/// generates a salt whose checksummed address follows a casing mask.
+///
+/// the mask is matched against the letters of the eip-55 address in
+/// order, skipping digits, which have no case: the first mask character
+/// applies to the first letter, the second to the second letter, and so
+/// on. 'U' needs an uppercase letter, 'l' a lowercase letter and '.'
+/// accepts anything. a mask shorter than the number of letters leaves the
+/// rest free, while an address with too few letters for the constrained
+/// part of the mask never matches.
+///
+/// each letter's case is set by one bit of the address hash, so every 'U'
+/// or 'l' halves the odds; twenty of them need about a million attempts,
+/// and masks longer than the ~15 letters of a typical address get steeply
+/// harder because the address also has to have enough letters.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - mask: casing pattern of 'U', 'l' and '.' (max 40 characters).
+///
+/// returns: the matching salt and its address.
+#[cfg(feature = "std")]
+pub fn generate_salt_checksum_mask(
+    deployer: &[u8],
+    mask: &str,
+) -> Result<SaltResult, Create3GenerateSaltError> {
+    if mask.len() > 40 {
+        return Err(Create3GenerateSaltError::MaskTooLong);
+    }
+    if !mask.chars().all(|c| matches!(c, 'U' | 'l' | '.')) {
+        return Err(Create3GenerateSaltError::InvalidMaskCharacter);
+    }
+    check_deployer(deployer)?;
+    let mut rng = search_rng();
+    let mut attempts: u64 = 0;
+    loop {
+        let salt = random_salt(&mut rng, 10);
+        let address = calc_addr(deployer, salt.as_bytes());
+        attempts += 1;
+        if matches_mask(&to_checksum_address(&address), mask) {
+            return Ok(SaltResult {
+                salt_hash: Keccak256::digest(&salt).into(),
+                salt,
+                address,
+                attempts,
+            });
+        }
+    }
+}
+
+/// generates a salt whose checksummed address starts with a prefix,
+/// matching the case of every letter.
+///
+/// each letter in the prefix must also land in the right case, which
+/// doubles the expected attempts for it compared to `generate_salt`.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired eip-55 address prefix, e.g. "DeAd" (1 to 20
+///   characters, with or without '0x').
+///
+/// returns: the matching salt and its address.
+#[cfg(feature = "std")]
+pub fn generate_salt_checksum_prefix(
+    deployer: &[u8],
+    prefix: &str,
+) -> Result<SaltResult, Create3GenerateSaltError> {
+    let prefix = sanitize_checksum_prefix(prefix)?;
+    check_deployer(deployer)?;
+    let mut rng = search_rng();
+    let mut attempts: u64 = 0;
+    loop {
+        let salt = random_salt(&mut rng, 10);
+        let address = calc_addr(deployer, salt.as_bytes());
+        attempts += 1;
+        if to_checksum_address(&address)[2..].starts_with(&prefix) {
+            return Ok(SaltResult {
+                salt_hash: Keccak256::digest(&salt).into(),
+                salt,
+                address,
+                attempts,
+            });
+        }
+    }
+}
+
+/// checks the letters of a checksummed address against a casing mask.
+#[cfg(feature = "std")]
+fn matches_mask(checksummed: &str, mask: &str) -> bool {
+    let mut letters =
+        checksummed[2..].chars().filter(|c| c.is_ascii_alphabetic());
+    mask.chars().all(|m| match (m, letters.next()) {
+        ('.', _) => true,
+        ('U', Some(c)) => c.is_ascii_uppercase(),
+        ('l', Some(c)) => c.is_ascii_lowercase(),
+        _ => false,
+    })
+}
+
+/// checks that the last `count` letters of a checksummed address are all
+/// uppercase.
+#[cfg(feature = "std")]
+fn has_caps_suffix(checksummed: &str, count: usize) -> bool {
+    let letters: Vec<char> = checksummed[2..]
+        .chars()
+        .rev()
+        .filter(|c| c.is_ascii_alphabetic())
+        .take(count)
+        .collect();
+    letters.len() == count && letters.iter().all(|c| c.is_ascii_uppercase())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{
+        calc_addr,
+        checksum::{
+            generate_salt_checksum_caps_suffix, generate_salt_checksum_mask,
+            generate_salt_checksum_prefix, matches_mask, to_checksum_address,
+        },
+        errors::Create3GenerateSaltError,
+    };
+
+    #[test]
+    fn should_checksum_known_addresses() {
+        let runs = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for run in runs.iter() {
+            let address = hex::decode(&run[2..]).unwrap();
+            assert_eq!(&to_checksum_address(&address), run);
+        }
+    }
+
+    #[test]
+    fn should_generate_checksum_prefix_in_exact_case() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let result = generate_salt_checksum_prefix(&deployer, "0xAb").unwrap();
+        assert!(to_checksum_address(&result.address).starts_with("0xAb"));
+        assert_eq!(
+            result.address,
+            calc_addr(&deployer, result.salt.as_bytes())
+        );
+        assert_eq!(
+            generate_salt_checksum_prefix(&deployer, "Ag"),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+        assert_eq!(
+            generate_salt_checksum_prefix(&deployer, "0x"),
+            Err(Create3GenerateSaltError::EmptyChecksumPrefix)
+        );
+    }
+
+    #[test]
+    fn should_generate_checksum_caps_suffix() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let result = generate_salt_checksum_caps_suffix(&deployer, 4).unwrap();
+        assert_eq!(
+            calc_addr(&deployer, result.salt.as_bytes()),
+            result.address
+        );
+        let letters: Vec<char> = to_checksum_address(&result.address)[2..]
+            .chars()
+            .filter(|c| c.is_ascii_alphabetic())
+            .collect();
+        assert!(letters.len() >= 4);
+        assert!(letters[letters.len() - 4..]
+            .iter()
+            .all(|c| c.is_ascii_uppercase()));
+    }
+
+    #[test]
+    fn generate_salt_checksum_caps_suffix_should_error_if_count_is_too_large() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        assert_eq!(
+            generate_salt_checksum_caps_suffix(&deployer, 41),
+            Err(Create3GenerateSaltError::CountTooLarge)
+        );
+    }
+
+    #[test]
+    fn should_generate_checksum_mask() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for mask in ["U", "lU", "..U", "", "UUUU"] {
+            let result = generate_salt_checksum_mask(&deployer, mask).unwrap();
+            assert_eq!(
+                calc_addr(&deployer, result.salt.as_bytes()),
+                result.address
+            );
+            let checksummed = to_checksum_address(&result.address);
+            let letters =
+                checksummed[2..].chars().filter(|c| c.is_ascii_alphabetic());
+            for (c, m) in letters.zip(mask.chars()) {
+                match m {
+                    'U' => assert!(c.is_ascii_uppercase()),
+                    'l' => assert!(c.is_ascii_lowercase()),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn checksum_mask_should_skip_digits() {
+        // letters are a, B, c, D; the digits in between take no mask
+        // character.
+        let checksummed = "0x1a2B3c4D";
+        assert!(matches_mask(checksummed, "lUlU"));
+        assert!(matches_mask(checksummed, ".U.U"));
+        assert!(!matches_mask(checksummed, "U"));
+        // an address that starts with a digit can still match "UU".
+        assert!(matches_mask("0x12ABcd", "UU"));
+        // more constraints than letters never match; trailing '.' does.
+        assert!(!matches_mask(checksummed, "lUlUl"));
+        assert!(matches_mask(checksummed, "lUlU.."));
+    }
+
+    #[test]
+    fn generate_salt_checksum_mask_should_error_on_invalid_mask() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        assert_eq!(
+            generate_salt_checksum_mask(&deployer, &".".repeat(41)),
+            Err(Create3GenerateSaltError::MaskTooLong)
+        );
+        assert_eq!(
+            generate_salt_checksum_mask(&deployer, "Ux"),
+            Err(Create3GenerateSaltError::InvalidMaskCharacter)
+        );
+    }
+}
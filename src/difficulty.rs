@@ -0,0 +1,187 @@
+use crate::{errors::Create3GenerateSaltError, sanitize_hex_prefix};
+
+/// estimates how many salts a prefix search takes on average.
+///
+/// arguments:
+/// - prefix: desired address prefix (without '0x').
+///
+/// returns: mean number of attempts for a uniformly random address,
+/// i.e. 16^len(prefix).
+pub fn expected_attempts(
+    prefix: &str,
+) -> Result<f64, Create3GenerateSaltError> {
+    let prefix = sanitize_hex_prefix(prefix)?;
+    Ok(16f64.powi(prefix.len() as i32))
+}
+
+/// estimates how many salts a combined prefix and suffix search takes on
+/// average.
+///
+/// arguments:
+/// - prefix: desired address prefix (without '0x').
+/// - suffix: desired address suffix.
+///
+/// returns: the product of the prefix and suffix difficulties.
+pub fn expected_attempts_prefix_suffix(
+    prefix: &str,
+    suffix: &str,
+) -> Result<f64, Create3GenerateSaltError> {
+    Ok(expected_attempts(prefix)? * expected_attempts(suffix)?)
+}
+
+/// estimates how many salts a prefix search needs to succeed with a given
+/// probability.
+///
+/// the number of attempts is geometric, so the mean understates the tail:
+/// a search for "0000" has a 10% chance of needing over 150k attempts even
+/// though it takes 65k on average.
+///
+/// arguments:
+/// - prefix: desired address prefix (without '0x').
+/// - p: probability of having found a match, in (0, 1).
+///
+/// returns: attempts needed to have found a match with probability `p`,
+/// i.e. ln(1 - p) / ln(1 - 1/16^len(prefix)) rounded up.
+pub fn attempt_percentile(
+    prefix: &str,
+    p: f64,
+) -> Result<u64, Create3GenerateSaltError> {
+    if !(p > 0.0 && p < 1.0) {
+        return Err(Create3GenerateSaltError::InvalidPercentile);
+    }
+    let match_odds = 1.0 / expected_attempts(prefix)?;
+    if match_odds == 1.0 {
+        return Ok(1);
+    }
+    // ln_1p keeps precision for the tiny odds of long prefixes.
+    Ok(((-p).ln_1p() / (-match_odds).ln_1p()).ceil() as u64)
+}
+
+/// estimates the chance that a prefix search with a limited budget finds
+/// a match.
+///
+/// arguments:
+/// - prefix: desired address prefix (without '0x').
+/// - attempts: number of salts the search may try.
+///
+/// returns: probability of at least one match, i.e.
+/// 1 - (1 - 1/16^len(prefix))^attempts.
+pub fn success_probability(
+    prefix: &str,
+    attempts: u64,
+) -> Result<f64, Create3GenerateSaltError> {
+    Ok(success_probability_for_odds(
+        1.0 / expected_attempts(prefix)?,
+        attempts,
+    ))
+}
+
+/// estimates the chance that a combined prefix and suffix search with a
+/// limited budget finds a match.
+///
+/// arguments:
+/// - prefix: desired address prefix (without '0x').
+/// - suffix: desired address suffix.
+/// - attempts: number of salts the search may try.
+///
+/// returns: probability of at least one match.
+pub fn success_probability_prefix_suffix(
+    prefix: &str,
+    suffix: &str,
+    attempts: u64,
+) -> Result<f64, Create3GenerateSaltError> {
+    Ok(success_probability_for_odds(
+        1.0 / expected_attempts_prefix_suffix(prefix, suffix)?,
+        attempts,
+    ))
+}
+
+/// probability of at least one hit in `attempts` tries at `match_odds`.
+fn success_probability_for_odds(match_odds: f64, attempts: u64) -> f64 {
+    if match_odds == 1.0 {
+        return if attempts > 0 { 1.0 } else { 0.0 };
+    }
+    // ln_1p and exp_m1 keep precision for the tiny odds of long prefixes.
+    -(attempts as f64 * (-match_odds).ln_1p()).exp_m1()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        difficulty::{
+            attempt_percentile, expected_attempts,
+            expected_attempts_prefix_suffix, success_probability,
+            success_probability_prefix_suffix,
+        },
+        errors::Create3GenerateSaltError,
+    };
+
+    #[test]
+    fn should_estimate_expected_attempts() {
+        assert_eq!(expected_attempts(""), Ok(1.0));
+        assert_eq!(expected_attempts("a"), Ok(16.0));
+        assert_eq!(expected_attempts("dEaD"), Ok(65536.0));
+        assert_eq!(expected_attempts("00000000"), Ok(4294967296.0));
+        assert_eq!(
+            expected_attempts("xyz"),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+
+    #[test]
+    fn should_estimate_expected_attempts_for_prefix_and_suffix() {
+        assert_eq!(expected_attempts_prefix_suffix("de", "ad"), Ok(65536.0));
+        assert_eq!(expected_attempts_prefix_suffix("", "beef"), Ok(65536.0));
+        assert_eq!(
+            expected_attempts_prefix_suffix("de", "ag"),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+
+    #[test]
+    fn should_estimate_attempt_percentiles() {
+        assert_eq!(attempt_percentile("a", 0.5), Ok(11));
+        assert_eq!(attempt_percentile("a", 0.9), Ok(36));
+        assert_eq!(attempt_percentile("", 0.99), Ok(1));
+        let median = attempt_percentile("0000", 0.5).unwrap();
+        let p90 = attempt_percentile("0000", 0.9).unwrap();
+        assert!(median < 65536 && p90 > 65536 * 2);
+        // 20 characters still resolves instead of dividing by zero.
+        assert!(attempt_percentile(&"f".repeat(20), 0.5).unwrap() > 0);
+        for p in [0.0, 1.0, -0.5, f64::NAN] {
+            assert_eq!(
+                attempt_percentile("a", p),
+                Err(Create3GenerateSaltError::InvalidPercentile)
+            );
+        }
+        assert_eq!(
+            attempt_percentile("xyz", 0.5),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+
+    #[test]
+    fn should_estimate_success_probability() {
+        assert_eq!(success_probability("", 0), Ok(0.0));
+        assert_eq!(success_probability("", 1), Ok(1.0));
+        assert_eq!(success_probability("a", 0), Ok(0.0));
+        assert!(
+            (success_probability("a", 1).unwrap() - 1.0 / 16.0).abs() < 1e-12
+        );
+        assert!(success_probability("a", 200).unwrap() > 0.999);
+        // the median from attempt_percentile is where the odds cross 50%.
+        let median = attempt_percentile("0000", 0.5).unwrap();
+        assert!(success_probability("0000", median).unwrap() >= 0.5);
+        assert!(success_probability("0000", median - 1).unwrap() < 0.5);
+        let long = success_probability(&"f".repeat(16), 1_000_000).unwrap();
+        assert!(long > 0.0 && long < 1e-12);
+        assert_eq!(
+            success_probability_prefix_suffix("de", "ad", 1_000),
+            success_probability("dead", 1_000)
+        );
+        assert_eq!(
+            success_probability("xyz", 1),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+}
@@ -1,4 +1,8 @@
-use std::{error::Error, fmt::Display};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::error::Error;
 
 /// errors for generating a create3 salt.
 #[derive(Debug, PartialEq)]
@@ -7,8 +11,63 @@ pub enum Create3GenerateSaltError {
     PrefixTooLong,
     /// prefix is not hex encoded.
     PrefixNotHexEncoded,
+    /// every counter value was tried without a match.
+    CounterExhausted,
+    /// search was cancelled before a match was found.
+    Cancelled,
+    /// attempt budget ran out before a match was found.
+    NotFound { attempts: u64 },
+    /// salt alphabet has no characters.
+    EmptyAlphabet,
+    /// salt alphabet contains non-printable bytes.
+    NonPrintableAlphabet { bytes: Vec<u8> },
+    /// integer salt is not a decimal or 0x-prefixed hex number.
+    InvalidIntSalt,
+    /// integer salt does not fit in 256 bits.
+    IntSaltTooLarge,
+    /// requested character count exceeds the 40 characters of an address.
+    CountTooLarge,
+    /// checksum mask is longer than the 40 characters of an address.
+    MaskTooLong,
+    /// checksum mask has a character other than 'U', 'l' or '.'.
+    InvalidMaskCharacter,
+    /// deployer address is not 20 bytes long.
+    InvalidDeployerLength { got: usize },
+    /// percentile is not strictly between 0 and 1.
+    InvalidPercentile,
+    /// salt length and charset allow fewer salts than the prefix needs.
+    SaltSpaceTooSmall,
+    /// address is not '0x' followed by 40 hex characters.
+    InvalidAddress,
+    /// deployer is not 40 hex characters, with or without '0x'.
+    InvalidDeployer,
+    /// a mining thread panicked before the search finished.
+    WorkerPanicked,
+    /// pattern has a character other than a hex digit or '?'.
+    InvalidPatternCharacter,
+    /// salt hash is not 64 hex characters, with or without '0x'.
+    InvalidSaltHash,
+    /// no needles were given to search for.
+    NoNeedles,
+    /// word has a letter with no hex look-alike.
+    UnmappableWord,
+    /// thread count is zero where it isn't read as "every core".
+    InvalidThreadCount,
+    /// salt prefix is longer than `MAX_SALT_PREFIX_LEN` bytes.
+    SaltPrefixTooLong,
+    /// salt is not an even number of hex characters.
+    SaltNotHexEncoded,
+    /// uppercase hex prefix has a lowercase letter, which can never match.
+    PrefixNotUppercase,
+    /// mixed-case address doesn't match its eip-55 checksum.
+    ChecksumMismatch { expected: String },
+    /// checksum prefix is empty, so it has no case to match.
+    EmptyChecksumPrefix,
+    /// salt makes the factory revert, or its guarded form needs a chain id.
+    UnsupportedFactorySalt,
 }
 
+#[cfg(feature = "std")]
 impl Error for Create3GenerateSaltError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         None
@@ -16,14 +75,107 @@ impl Error for Create3GenerateSaltError {
 }
 
 impl Display for Create3GenerateSaltError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(match self {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
             Create3GenerateSaltError::PrefixTooLong => {
-                "prefix too long (max 20 bytes)."
+                f.write_str("prefix too long (max 20 bytes).")
             }
             Create3GenerateSaltError::PrefixNotHexEncoded => {
-                "prefix not hex encoded."
+                f.write_str("prefix not hex encoded.")
             }
-        })
+            Create3GenerateSaltError::CounterExhausted => {
+                f.write_str("counter exhausted without a match.")
+            }
+            Create3GenerateSaltError::Cancelled => {
+                f.write_str("search cancelled.")
+            }
+            Create3GenerateSaltError::NotFound { attempts } => {
+                write!(f, "no match found after {} attempts.", attempts)
+            }
+            Create3GenerateSaltError::EmptyAlphabet => {
+                f.write_str("salt alphabet is empty.")
+            }
+            Create3GenerateSaltError::NonPrintableAlphabet { bytes } => {
+                write!(
+                    f,
+                    "salt alphabet has non-printable bytes: 0x{}.",
+                    hex::encode(bytes)
+                )
+            }
+            Create3GenerateSaltError::InvalidIntSalt => {
+                f.write_str("salt is not a decimal or hex integer.")
+            }
+            Create3GenerateSaltError::IntSaltTooLarge => {
+                f.write_str("salt integer too large (max 256 bits).")
+            }
+            Create3GenerateSaltError::CountTooLarge => {
+                f.write_str("count too large (max 40 characters).")
+            }
+            Create3GenerateSaltError::MaskTooLong => {
+                f.write_str("mask too long (max 40 characters).")
+            }
+            Create3GenerateSaltError::InvalidMaskCharacter => {
+                f.write_str("mask may only contain 'U', 'l' and '.'.")
+            }
+            Create3GenerateSaltError::InvalidDeployerLength { got } => {
+                write!(f, "deployer must be 20 bytes, got {}.", got)
+            }
+            Create3GenerateSaltError::InvalidPercentile => {
+                f.write_str("percentile must be between 0 and 1.")
+            }
+            Create3GenerateSaltError::SaltSpaceTooSmall => {
+                f.write_str("too few possible salts for this prefix.")
+            }
+            Create3GenerateSaltError::InvalidAddress => f.write_str(
+                "address must be '0x' followed by 40 hex characters.",
+            ),
+            Create3GenerateSaltError::InvalidDeployer => {
+                f.write_str("deployer must be 40 hex characters.")
+            }
+            Create3GenerateSaltError::WorkerPanicked => {
+                f.write_str("a mining thread panicked.")
+            }
+            Create3GenerateSaltError::InvalidPatternCharacter => {
+                f.write_str("pattern may only contain hex digits and '?'.")
+            }
+            Create3GenerateSaltError::InvalidSaltHash => {
+                f.write_str("salt hash must be 64 hex characters.")
+            }
+            Create3GenerateSaltError::NoNeedles => {
+                f.write_str("at least one needle is required.")
+            }
+            Create3GenerateSaltError::UnmappableWord => {
+                f.write_str("word has letters with no hex look-alike.")
+            }
+            Create3GenerateSaltError::InvalidThreadCount => {
+                f.write_str("thread count must be at least 1.")
+            }
+            Create3GenerateSaltError::SaltPrefixTooLong => {
+                write!(
+                    f,
+                    "salt prefix was too long (over {} bytes).",
+                    crate::MAX_SALT_PREFIX_LEN
+                )
+            }
+            Create3GenerateSaltError::SaltNotHexEncoded => {
+                f.write_str("salt not hex encoded.")
+            }
+            Create3GenerateSaltError::PrefixNotUppercase => {
+                f.write_str("prefix must be uppercase hex.")
+            }
+            Create3GenerateSaltError::ChecksumMismatch { expected } => {
+                write!(
+                    f,
+                    "address checksum does not match, expected {}.",
+                    expected
+                )
+            }
+            Create3GenerateSaltError::EmptyChecksumPrefix => {
+                f.write_str("checksum prefix is empty.")
+            }
+            Create3GenerateSaltError::UnsupportedFactorySalt => f.write_str(
+                "factory rejects this salt or ties it to a chain id.",
+            ),
+        }
     }
 }
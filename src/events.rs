@@ -0,0 +1,176 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    calc_addr, check_deployer, difficulty::expected_attempts,
+    errors::Create3GenerateSaltError, matches_prefix, random_salt,
+    sanitize_hex_prefix, search_rng, to_nibbles, SaltResult,
+};
+
+// minimum time between two progress events.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// events emitted over the course of a salt search.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchEvent {
+    /// search began; mean attempts needed for a uniformly random address.
+    Started { estimated_attempts: f64 },
+    /// search is still running.
+    Progress { attempts: u64, elapsed: Duration },
+    /// a matching salt was found.
+    Found { result: SaltResult, attempts: u64 },
+    /// search was stopped through the stop flag.
+    Cancelled,
+    /// search ran out of time.
+    TimedOut,
+}
+
+/// generates a salt for a given prefix, reporting the search through
+/// events.
+///
+/// every search emits `Started` first and ends with exactly one of
+/// `Found`, `Cancelled` or `TimedOut`, with `Progress` roughly once a
+/// second in between.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - stop: flag which cancels the search once set.
+/// - timeout: optional limit on the search time.
+/// - on_event: callback receiving each event.
+///
+/// returns: the found salt, or None if the search was cancelled or timed out.
+pub fn generate_salt_with_events(
+    deployer: &[u8],
+    prefix: &str,
+    stop: &AtomicBool,
+    timeout: Option<Duration>,
+    mut on_event: impl FnMut(SearchEvent),
+) -> Result<Option<SaltResult>, Create3GenerateSaltError> {
+    let estimated_attempts = expected_attempts(prefix)?;
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(deployer)?;
+    on_event(SearchEvent::Started { estimated_attempts });
+    let start = Instant::now();
+    let mut last_progress = start;
+    let mut attempts: u64 = 0;
+    let mut rng = search_rng();
+    loop {
+        // check the flags before hashing so a raised stop is never missed.
+        if attempts % 256 == 0 {
+            if stop.load(Ordering::Relaxed) {
+                on_event(SearchEvent::Cancelled);
+                return Ok(None);
+            }
+            let elapsed = start.elapsed();
+            if timeout.is_some_and(|timeout| elapsed >= timeout) {
+                on_event(SearchEvent::TimedOut);
+                return Ok(None);
+            }
+            if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                last_progress = Instant::now();
+                on_event(SearchEvent::Progress { attempts, elapsed });
+            }
+        }
+        let salt = random_salt(&mut rng, 10);
+        let address = calc_addr(deployer, salt.as_bytes());
+        attempts += 1;
+        if matches_prefix(&address, &prefix) {
+            let result = SaltResult {
+                salt_hash: Keccak256::digest(&salt).into(),
+                salt,
+                address,
+                attempts,
+            };
+            on_event(SearchEvent::Found {
+                result: result.clone(),
+                attempts,
+            });
+            return Ok(Some(result));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+    use std::time::Duration;
+
+    use crate::{
+        calc_addr_with_bytes,
+        events::{generate_salt_with_events, SearchEvent},
+    };
+
+    #[test]
+    fn should_emit_started_and_found() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let mut events = Vec::new();
+        let result = generate_salt_with_events(
+            &deployer,
+            "ab",
+            &AtomicBool::new(false),
+            None,
+            |event| events.push(event),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            events.first(),
+            Some(&SearchEvent::Started {
+                estimated_attempts: 256.0
+            })
+        );
+        let Some(SearchEvent::Found {
+            result: found,
+            attempts,
+        }) = events.last()
+        else {
+            panic!("expected a found event");
+        };
+        assert_eq!(found, &result);
+        assert!(*attempts >= 1);
+        assert_eq!(
+            calc_addr_with_bytes(&deployer, &result.salt_hash),
+            result.address
+        );
+        assert!(hex::encode(result.address).starts_with("ab"));
+    }
+
+    #[test]
+    fn should_emit_cancelled_when_stopped() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let mut events = Vec::new();
+        let result = generate_salt_with_events(
+            &deployer,
+            "abcdef0123",
+            &AtomicBool::new(true),
+            None,
+            |event| events.push(event),
+        )
+        .unwrap();
+        assert_eq!(result, None);
+        assert_eq!(events.last(), Some(&SearchEvent::Cancelled));
+    }
+
+    #[test]
+    fn should_emit_timed_out_after_timeout() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let mut events = Vec::new();
+        let result = generate_salt_with_events(
+            &deployer,
+            "abcdef0123",
+            &AtomicBool::new(false),
+            Some(Duration::from_millis(10)),
+            |event| events.push(event),
+        )
+        .unwrap();
+        assert_eq!(result, None);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events.last(), Some(&SearchEvent::TimedOut));
+    }
+}
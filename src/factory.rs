@@ -0,0 +1,281 @@
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    calc_create2_addr, errors::Create3GenerateSaltError, final_create_preimage,
+    KECCAK256_PROXY_CHILD_BYTECODE,
+};
+
+/// address of the createx factory, the same on every chain it is deployed
+/// to (`CreateX.sol` in pcaversaccio/createx, see its deployments list).
+pub const CREATEX_ADDRESS: [u8; 20] = [
+    0xba, 0x5e, 0xd0, 0x99, 0x63, 0x3d, 0x3b, 0x31, 0x3e, 0x4d, 0x5f, 0x7b,
+    0xdc, 0x13, 0x05, 0xd3, 0xc2, 0x8b, 0xa5, 0xed,
+];
+
+/// create3 factories whose proxy and salt handling are built in.
+///
+/// a zero0age preset was requested as well but is not shipped: there is no
+/// verified proxy hash for it. `calc_addr_with_proxy_hash` covers factories
+/// that only differ in their proxy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Factory {
+    /// plain create3 as in 0xsequence/create3 and solmate's
+    /// `src/utils/CREATE3.sol`: `deployer` runs create2 for the proxy with
+    /// the salt as is. this is the derivation `calc_addr` uses.
+    Default,
+    /// solady's `src/utils/CREATE3.sol` library. it deploys the same proxy
+    /// and uses the salt as is, so it gives the same address as `Default`.
+    Solady,
+    /// createx (`src/CreateX.sol`) at `CREATEX_ADDRESS`. `deployer` is the
+    /// account calling `deployCreate3`; the salt goes through createx's
+    /// `_guard` before the factory itself runs create2 for the proxy.
+    CreateX,
+}
+
+impl Factory {
+    /// returns the keccak256 digest of the proxy init code this factory
+    /// deploys with create2.
+    pub fn proxy_hash(&self) -> [u8; 32] {
+        match self {
+            // keccak256 of the proxy 0x67363d3d37363d34f03d5260086018f3.
+            Factory::Default => KECCAK256_PROXY_CHILD_BYTECODE,
+            // `PROXY_INITCODE_HASH` in solady's `CREATE3.sol`:
+            // 0x21c35dbe1b344a2488cf3321d6ce542f8e9f305544ff09e4993a62319a497c1f.
+            Factory::Solady => KECCAK256_PROXY_CHILD_BYTECODE,
+            // keccak256 of `proxyChildBytecode` in createx's
+            // `deployCreate3` and `computeCreate3Address`, the same proxy.
+            Factory::CreateX => KECCAK256_PROXY_CHILD_BYTECODE,
+        }
+    }
+}
+
+/// applies createx's `_guard` to a salt passed to `deployCreate3`.
+///
+/// the first 20 bytes of the salt pick the protection: the caller's address
+/// with byte 21 set to 0x00 binds the salt to the caller, which is hashed in
+/// as `keccak256(abi.encode(caller, salt))`. any other salt that createx
+/// accepts without a chain id is re-hashed as `keccak256(abi.encode(salt))`.
+///
+/// arguments:
+/// - caller: account calling createx (bytes).
+/// - salt: 32-byte salt passed to createx.
+///
+/// returns: the guarded salt createx runs create2 with, or
+/// `UnsupportedFactorySalt` when byte 21 asks for cross-chain redeploy
+/// protection (the guarded salt then depends on the chain id) or is not
+/// 0x00 or 0x01 behind the caller's or the zero address (createx reverts).
+pub fn createx_guarded_salt(
+    caller: &[u8],
+    salt: &[u8; 32],
+) -> Result<[u8; 32], Create3GenerateSaltError> {
+    let sender = &salt[..20];
+    let flag = salt[20];
+    if sender == caller {
+        if flag != 0x00 {
+            return Err(Create3GenerateSaltError::UnsupportedFactorySalt);
+        }
+        let mut encoded = [0u8; 64];
+        encoded[32 - caller.len()..32].copy_from_slice(caller);
+        encoded[32..].copy_from_slice(salt);
+        return Ok(Keccak256::digest(encoded).into());
+    }
+    if sender == [0u8; 20] && flag != 0x00 {
+        return Err(Create3GenerateSaltError::UnsupportedFactorySalt);
+    }
+    Ok(Keccak256::digest(salt).into())
+}
+
+/// calculates the address of a contract deployed through `factory`.
+///
+/// arguments:
+/// - factory: create3 factory the deployment goes through.
+/// - deployer: create3 deployer address (bytes); for createx, the account
+///   calling it.
+/// - salt: 32-byte value passed to the factory as the salt.
+///
+/// returns: a 20-byte contract address, or `UnsupportedFactorySalt` when
+/// createx would reject the salt or tie it to a chain id.
+pub fn calc_addr_for(
+    factory: Factory,
+    deployer: &[u8],
+    salt: &[u8; 32],
+) -> Result<[u8; 20], Create3GenerateSaltError> {
+    match factory {
+        Factory::Default | Factory::Solady => Ok(calc_addr_with_proxy_hash(
+            deployer,
+            salt,
+            &factory.proxy_hash(),
+        )),
+        Factory::CreateX => {
+            let guarded = createx_guarded_salt(deployer, salt)?;
+            Ok(calc_addr_with_proxy_hash(
+                &CREATEX_ADDRESS,
+                &guarded,
+                &factory.proxy_hash(),
+            ))
+        }
+    }
+}
+
+/// calculates the address of a contract deployed through a factory whose
+/// proxy init code hashes to `proxy_hash`.
+///
+/// the salt is used as is; this is for factories that only differ from
+/// `Factory::Default` in their proxy.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - salt: 32-byte value used as the salt.
+/// - proxy_hash: keccak256 digest of the proxy init code.
+///
+/// returns: a 20-byte contract address.
+pub fn calc_addr_with_proxy_hash(
+    deployer: &[u8],
+    salt: &[u8; 32],
+    proxy_hash: &[u8; 32],
+) -> [u8; 20] {
+    let proxy = calc_create2_addr(deployer, salt, proxy_hash);
+    let hash = Keccak256::digest(final_create_preimage(&proxy));
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use sha3::{Digest, Keccak256};
+
+    use crate::{
+        calc_addr_with_bytes,
+        checksum::to_checksum_address,
+        errors::Create3GenerateSaltError,
+        factory::{
+            calc_addr_for, calc_addr_with_proxy_hash, createx_guarded_salt,
+            Factory, CREATEX_ADDRESS,
+        },
+        KECCAK256_PROXY_CHILD_BYTECODE,
+    };
+
+    #[test]
+    fn default_proxy_hash_should_hash_the_shared_proxy_init_code() {
+        let init_code =
+            hex::decode("67363d3d37363d34f03d5260086018f3").unwrap();
+        let proxy_hash: [u8; 32] = Keccak256::digest(init_code).into();
+        assert_eq!(proxy_hash, KECCAK256_PROXY_CHILD_BYTECODE);
+    }
+
+    #[test]
+    fn should_calculate_address_for_proxy_hash() {
+        let deployer: Vec<u8> =
+            hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8").unwrap();
+        let salt: [u8; 32] = Keccak256::digest("nacl").into();
+        assert_eq!(
+            calc_addr_with_proxy_hash(
+                &deployer,
+                &salt,
+                &KECCAK256_PROXY_CHILD_BYTECODE
+            ),
+            calc_addr_with_bytes(&deployer, &salt)
+        );
+        // a different proxy changes the address.
+        assert_ne!(
+            calc_addr_with_proxy_hash(&deployer, &salt, &[0x11; 32]),
+            calc_addr_with_bytes(&deployer, &salt)
+        );
+    }
+
+    #[test]
+    fn createx_address_should_match_its_checksummed_form() {
+        assert_eq!(
+            to_checksum_address(&CREATEX_ADDRESS),
+            "0xba5Ed099633D3B313e4D5F7bdc1305d3c28ba5Ed"
+        );
+    }
+
+    #[test]
+    fn presets_should_hash_the_proxy_they_deploy() {
+        let solady: [u8; 32] = hex::decode(
+            "21c35dbe1b344a2488cf3321d6ce542f8e9f305544ff09e4993a62319a497c1f",
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        for factory in [Factory::Default, Factory::Solady, Factory::CreateX] {
+            assert_eq!(factory.proxy_hash(), solady);
+        }
+    }
+
+    #[test]
+    fn each_preset_should_yield_its_own_address() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let salt: [u8; 32] = Keccak256::digest("nacl").into();
+        let default = calc_addr_for(Factory::Default, &deployer, &salt);
+        let createx = calc_addr_for(Factory::CreateX, &deployer, &salt);
+        assert_eq!(default, Ok(calc_addr_with_bytes(&deployer, &salt)));
+        assert_ne!(createx, default);
+        // solady deploys the same proxy with the raw salt, so it can't
+        // differ from the default derivation.
+        assert_eq!(calc_addr_for(Factory::Solady, &deployer, &salt), default);
+    }
+
+    #[test]
+    fn createx_should_derive_from_its_own_address_and_guarded_salt() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let salt: [u8; 32] = Keccak256::digest("nacl").into();
+        let guarded: [u8; 32] = Keccak256::digest(salt).into();
+        assert_eq!(createx_guarded_salt(&deployer, &salt), Ok(guarded));
+        assert_eq!(
+            calc_addr_for(Factory::CreateX, &deployer, &salt),
+            Ok(calc_addr_with_bytes(&CREATEX_ADDRESS, &guarded))
+        );
+        // a random salt doesn't depend on who calls createx.
+        assert_eq!(
+            calc_addr_for(Factory::CreateX, &[0x11; 20], &salt),
+            calc_addr_for(Factory::CreateX, &deployer, &salt)
+        );
+    }
+
+    #[test]
+    fn createx_should_bind_a_permissioned_salt_to_the_caller() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let mut salt = [0x42u8; 32];
+        salt[..20].copy_from_slice(&deployer);
+        salt[20] = 0x00;
+        let mut encoded = vec![0u8; 12];
+        encoded.extend_from_slice(&deployer);
+        encoded.extend_from_slice(&salt);
+        let guarded: [u8; 32] = Keccak256::digest(&encoded).into();
+        assert_eq!(createx_guarded_salt(&deployer, &salt), Ok(guarded));
+        // from another caller the same salt is just re-hashed.
+        let rehashed: [u8; 32] = Keccak256::digest(salt).into();
+        assert_eq!(createx_guarded_salt(&[0x11; 20], &salt), Ok(rehashed));
+        // the zero address without redeploy protection is re-hashed too.
+        let mut zero = [0x42u8; 32];
+        zero[..21].fill(0);
+        let rehashed: [u8; 32] = Keccak256::digest(zero).into();
+        assert_eq!(createx_guarded_salt(&deployer, &zero), Ok(rehashed));
+    }
+
+    #[test]
+    fn createx_should_reject_chain_bound_and_reverting_salts() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for (sender, flag) in [
+            (deployer.as_slice(), 0x01),
+            (deployer.as_slice(), 0x02),
+            (&[0u8; 20][..], 0x01),
+            (&[0u8; 20][..], 0x02),
+        ] {
+            let mut salt = [0x42u8; 32];
+            salt[..20].copy_from_slice(sender);
+            salt[20] = flag;
+            assert_eq!(
+                calc_addr_for(Factory::CreateX, &deployer, &salt),
+                Err(Create3GenerateSaltError::UnsupportedFactorySalt)
+            );
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! c abi wrappers around the address derivation functions, for calling
+//! create3 from c, swift or other hosts.
+//!
+//! build a linkable library with e.g.
+//! `cargo rustc --release --features ffi --crate-type cdylib`.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+
+use crate::{calc_addr, check_deployer};
+
+/// the address was written to the output buffer.
+pub const CREATE3_OK: i32 = 0;
+/// a pointer argument was null.
+pub const CREATE3_ERR_NULL_POINTER: i32 = 1;
+/// the deployer was not 20 bytes long.
+pub const CREATE3_ERR_INVALID_DEPLOYER: i32 = 2;
+/// the calculation panicked; the output buffer is left untouched.
+pub const CREATE3_ERR_PANIC: i32 = 3;
+
+/// calculates the create3 address for a deployer and a salt, hashing the
+/// salt like `calc_addr`.
+///
+/// arguments:
+/// - deployer_ptr, deployer_len: create3 deployer address (20 bytes).
+/// - salt_ptr, salt_len: salt bytes; may be null if salt_len is 0.
+/// - out_20: buffer the 20-byte address is written to.
+///
+/// returns: `CREATE3_OK`, or one of the `CREATE3_ERR_*` codes.
+///
+/// # Safety
+///
+/// `deployer_ptr` and `salt_ptr` must be valid for reads of `deployer_len`
+/// and `salt_len` bytes, and `out_20` must be valid for writes of 20
+/// bytes, for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn create3_calc_addr(
+    deployer_ptr: *const u8,
+    deployer_len: usize,
+    salt_ptr: *const u8,
+    salt_len: usize,
+    out_20: *mut u8,
+) -> i32 {
+    if deployer_ptr.is_null() || out_20.is_null() {
+        return CREATE3_ERR_NULL_POINTER;
+    }
+    let salt: &[u8] = if salt_len == 0 {
+        &[]
+    } else if salt_ptr.is_null() {
+        return CREATE3_ERR_NULL_POINTER;
+    } else {
+        slice::from_raw_parts(salt_ptr, salt_len)
+    };
+    let deployer = slice::from_raw_parts(deployer_ptr, deployer_len);
+    if check_deployer(deployer).is_err() {
+        return CREATE3_ERR_INVALID_DEPLOYER;
+    }
+    // unwinding across the c abi is undefined behaviour.
+    match panic::catch_unwind(AssertUnwindSafe(|| calc_addr(deployer, salt))) {
+        Ok(address) => {
+            slice::from_raw_parts_mut(out_20, 20).copy_from_slice(&address);
+            CREATE3_OK
+        }
+        Err(_) => CREATE3_ERR_PANIC,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use crate::{
+        calc_addr,
+        ffi::{
+            create3_calc_addr, CREATE3_ERR_INVALID_DEPLOYER,
+            CREATE3_ERR_NULL_POINTER, CREATE3_OK,
+        },
+    };
+
+    // the signature a c host would declare.
+    type CalcAddrFn = unsafe extern "C" fn(
+        *const u8,
+        usize,
+        *const u8,
+        usize,
+        *mut u8,
+    ) -> i32;
+
+    #[test]
+    fn should_calculate_address_through_c_abi() {
+        let calc: CalcAddrFn = create3_calc_addr;
+        let deployer =
+            hex::decode("De2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343B5").unwrap();
+        let salt = b"nacl";
+        let mut out = [0u8; 20];
+        let status = unsafe {
+            calc(
+                deployer.as_ptr(),
+                deployer.len(),
+                salt.as_ptr(),
+                salt.len(),
+                out.as_mut_ptr(),
+            )
+        };
+        assert_eq!(status, CREATE3_OK);
+        assert_eq!(
+            hex::encode(out),
+            "8b9A192B07bb8de5615545C620738c2713B97D4d".to_lowercase()
+        );
+
+        let status = unsafe {
+            calc(deployer.as_ptr(), 20, ptr::null(), 0, out.as_mut_ptr())
+        };
+        assert_eq!(status, CREATE3_OK);
+        assert_eq!(out, calc_addr(&deployer, b""));
+    }
+
+    #[test]
+    fn should_return_error_codes_for_bad_arguments() {
+        let calc: CalcAddrFn = create3_calc_addr;
+        let deployer = [0xab; 20];
+        let mut out = [0u8; 20];
+        unsafe {
+            assert_eq!(
+                calc(ptr::null(), 20, b"a".as_ptr(), 1, out.as_mut_ptr()),
+                CREATE3_ERR_NULL_POINTER
+            );
+            assert_eq!(
+                calc(deployer.as_ptr(), 20, ptr::null(), 1, out.as_mut_ptr()),
+                CREATE3_ERR_NULL_POINTER
+            );
+            assert_eq!(
+                calc(deployer.as_ptr(), 20, b"a".as_ptr(), 1, ptr::null_mut()),
+                CREATE3_ERR_NULL_POINTER
+            );
+            assert_eq!(
+                calc(deployer.as_ptr(), 19, b"a".as_ptr(), 1, out.as_mut_ptr()),
+                CREATE3_ERR_INVALID_DEPLOYER
+            );
+        }
+        assert_eq!(out, [0; 20]);
+    }
+}
@@ -0,0 +1,244 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    calc_addr, errors::Create3GenerateSaltError, random_salt, search_rng,
+    worker_panic_hook,
+};
+
+// time spent measuring each candidate thread count.
+const SAMPLE_WINDOW: Duration = Duration::from_millis(250);
+
+/// measures the aggregate salt search hashrate over a time window.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - thread_count: number of threads to hash with.
+/// - duration: how long to measure for.
+///
+/// returns: addresses computed per second, summed across all threads, or
+/// a `WorkerPanicked` error if a hashing thread panicked.
+pub fn measure_hashrate(
+    deployer: &[u8],
+    thread_count: usize,
+    duration: Duration,
+) -> Result<f64, Create3GenerateSaltError> {
+    let start = Instant::now();
+    let mut threads: Vec<thread::JoinHandle<u64>> = Vec::new();
+    for _ in 0..thread_count.max(1) {
+        let d = deployer.to_owned();
+        let handle = thread::spawn(move || {
+            worker_panic_hook(&d);
+            let mut rng = search_rng();
+            let mut attempts: u64 = 0;
+            loop {
+                calc_addr(&d, random_salt(&mut rng, 10).as_bytes());
+                attempts += 1;
+                // only check the clock every so often to keep it cheap.
+                if attempts % 256 == 0 && start.elapsed() >= duration {
+                    break;
+                }
+            }
+            attempts
+        });
+        threads.push(handle);
+    }
+    let mut attempts: u64 = 0;
+    let mut panicked = false;
+    for t in threads {
+        match t.join() {
+            Ok(tried) => attempts += tried,
+            Err(_) => panicked = true,
+        }
+    }
+    if panicked {
+        return Err(Create3GenerateSaltError::WorkerPanicked);
+    }
+    Ok(attempts as f64 / start.elapsed().as_secs_f64())
+}
+
+/// finds the thread count with the highest aggregate hashrate.
+///
+/// thread counts of 1, 2, 4, 8, ... up to the available cores are measured
+/// in turn, stopping as soon as adding threads no longer raises throughput.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+///
+/// returns: the best measured thread count, at most 255; a count whose
+/// measurement fails ends the search like a drop in throughput would.
+pub fn optimal_thread_count(deployer: &[u8]) -> u8 {
+    let cores = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(u8::MAX as usize) as u8;
+    let Ok(rate) = measure_hashrate(deployer, 1, SAMPLE_WINDOW) else {
+        return 1;
+    };
+    let mut best = (1, rate);
+    let mut count: u8 = 2;
+    while count <= cores {
+        let rate = measure_hashrate(deployer, count as usize, SAMPLE_WINDOW)
+            .unwrap_or(0.0);
+        if rate <= best.1 {
+            // contention has started eating into the gains, or a thread
+            // failed.
+            break;
+        }
+        best = (count, rate);
+        count = match count.checked_mul(2) {
+            Some(next) if next <= cores => next,
+            // always give the full core count a turn.
+            _ if count < cores => cores,
+            _ => break,
+        };
+    }
+    best.0
+}
+
+/// identifies the machine a hashrate was measured on.
+///
+/// returns: a short hex digest of the architecture, os and core count.
+fn machine_fingerprint() -> String {
+    let cores = thread::available_parallelism().map_or(1, |n| n.get());
+    let id = format!(
+        "{}-{}-{}",
+        std::env::consts::ARCH,
+        std::env::consts::OS,
+        cores
+    );
+    hex::encode(&Keccak256::digest(id)[0..8])
+}
+
+/// returns: the path of the hashrate cache file (~/.create3/hashrate).
+fn cache_path() -> Option<PathBuf> {
+    let home =
+        std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+    Some(PathBuf::from(home).join(".create3").join("hashrate"))
+}
+
+/// reads a cached hashrate, ignoring entries from another crate version
+/// or machine.
+fn load_cached_hashrate_from(path: &Path) -> Option<f64> {
+    let contents = fs::read_to_string(path).ok()?;
+    let mut fields = contents.split_whitespace();
+    if fields.next()? != env!("CARGO_PKG_VERSION")
+        || fields.next()? != machine_fingerprint()
+    {
+        return None;
+    }
+    fields
+        .next()?
+        .parse()
+        .ok()
+        .filter(|rate: &f64| rate.is_finite())
+}
+
+/// writes a hashrate along with the crate version and machine fingerprint.
+fn save_hashrate_to(path: &Path, hashrate: f64) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(
+        path,
+        format!(
+            "{} {} {}\n",
+            env!("CARGO_PKG_VERSION"),
+            machine_fingerprint(),
+            hashrate
+        ),
+    )
+}
+
+/// loads the hashrate cached by a previous run on this machine.
+///
+/// the cache is ignored if it was written by a different crate version.
+///
+/// returns: the cached hashrate, if present and valid.
+pub fn load_cached_hashrate() -> Option<f64> {
+    load_cached_hashrate_from(&cache_path()?)
+}
+
+/// caches a measured hashrate in ~/.create3/hashrate.
+///
+/// arguments:
+/// - hashrate: addresses computed per second.
+pub fn save_hashrate(hashrate: f64) -> io::Result<()> {
+    let path = cache_path().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, "home directory not found.")
+    })?;
+    save_hashrate_to(&path, hashrate)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::Duration;
+
+    use crate::{
+        errors::Create3GenerateSaltError,
+        hashrate::{
+            load_cached_hashrate_from, machine_fingerprint, measure_hashrate,
+            optimal_thread_count, save_hashrate_to,
+        },
+        PANICKING_DEPLOYER,
+    };
+
+    #[test]
+    fn should_measure_positive_hashrate() {
+        let deployer: Vec<u8> =
+            hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap();
+        for thread_count in [1, 2] {
+            let rate = measure_hashrate(
+                &deployer,
+                thread_count,
+                Duration::from_millis(50),
+            )
+            .unwrap();
+            assert!(rate.is_finite() && rate > 0.0);
+        }
+    }
+
+    #[test]
+    fn measure_hashrate_should_error_when_a_worker_panics() {
+        assert_eq!(
+            measure_hashrate(&PANICKING_DEPLOYER, 2, Duration::from_millis(50)),
+            Err(Create3GenerateSaltError::WorkerPanicked)
+        );
+        assert_eq!(optimal_thread_count(&PANICKING_DEPLOYER), 1);
+    }
+
+    #[test]
+    fn optimal_thread_count_should_be_within_available_cores() {
+        let deployer: Vec<u8> =
+            hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap();
+        let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let count = optimal_thread_count(&deployer);
+        assert!(count >= 1 && count as usize <= cores);
+    }
+
+    #[test]
+    fn should_reload_saved_hashrate() {
+        let path = std::env::temp_dir()
+            .join(format!("create3-hashrate-{}", std::process::id()))
+            .join("hashrate");
+        assert_eq!(load_cached_hashrate_from(&path), None);
+        save_hashrate_to(&path, 1234.5).unwrap();
+        assert_eq!(load_cached_hashrate_from(&path), Some(1234.5));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn should_ignore_cached_hashrate_from_other_version() {
+        let path = std::env::temp_dir()
+            .join(format!("create3-hashrate-stale-{}", std::process::id()));
+        fs::write(&path, format!("0.0.0 {} 1234.5\n", machine_fingerprint()))
+            .unwrap();
+        assert_eq!(load_cached_hashrate_from(&path), None);
+        fs::remove_file(&path).unwrap();
+    }
+}
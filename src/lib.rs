@@ -1,14 +1,82 @@
+//! create3 address derivation and vanity salt mining.
+//!
+//! the address derivation functions only need `alloc`, so building with
+//! `default-features = false` gives a `no_std` crate for wasm or embedded
+//! targets. salt generation needs threads and randomness and lives behind
+//! the default `std` feature.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod address;
+#[cfg(feature = "alloy")]
+pub mod alloy;
+#[cfg(feature = "std")]
+pub mod batch;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(all(feature = "std", feature = "serde"))]
+pub mod checkpoint;
+pub mod checksum;
+#[cfg(feature = "std")]
+pub mod difficulty;
 pub mod errors;
+#[cfg(feature = "std")]
+pub mod events;
+pub mod factory;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod hashrate;
+#[cfg(feature = "std")]
+pub mod miner;
+#[cfg(feature = "tokio")]
+pub mod nonblocking;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "std")]
+pub mod score;
+#[cfg(feature = "serde")]
+mod serde_hex;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod stream;
 
-use std::sync::{Arc, RwLock};
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+#[cfg(feature = "std")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "std")]
 use std::thread;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
 
+use address::Address;
 use errors::Create3GenerateSaltError;
-use rand::{distributions::Alphanumeric, Rng};
+#[cfg(feature = "std")]
+use miner::MatchIterator;
+#[cfg(feature = "std")]
+use rand::{distributions::Alphanumeric, rngs::SmallRng, Rng, SeedableRng};
 use sha3::{Digest, Keccak256};
+#[cfg(feature = "std")]
+use stats::MiningStats;
+
+// attempts a search worker claims from the shared budget at a time.
+#[cfg(feature = "std")]
+const SEARCH_CHUNK: u64 = 1024;
+
+// how often the calling thread checks on the workers while reporting
+// progress.
+#[cfg(feature = "std")]
+const PROGRESS_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 // proxy child bytecode; deployed bytecode does not affect the address.
-const KECCAK256_PROXY_CHILD_BYTECODE: [u8; 32] = [
+pub(crate) const KECCAK256_PROXY_CHILD_BYTECODE: [u8; 32] = [
     33, 195, 93, 190, 27, 52, 74, 36, 136, 207, 51, 33, 214, 206, 84, 47, 142,
     159, 48, 85, 68, 255, 9, 228, 153, 58, 98, 49, 154, 73, 124, 31,
 ];
@@ -28,6 +96,58 @@ pub fn calc_addr(deployer: &[u8], salt: &[u8]) -> [u8; 20] {
     )
 }
 
+/// `calc_addr` returning an `Address`, which displays checksummed.
+pub fn calc_addr_typed(deployer: &[u8], salt: &[u8]) -> Address {
+    Address(calc_addr(deployer, salt))
+}
+
+/// calculates the address of a contract for a string salt.
+///
+/// the utf8 bytes of the salt are hashed with keccak256 whatever its
+/// length; nothing is truncated or padded, so two salts differing only
+/// past the 32nd byte give different addresses. this is the salt string
+/// the cli and the generators print.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - salt: salt string of any length.
+///
+/// returns: a 20-byte contract address.
+pub fn calc_addr_str(deployer: &[u8], salt: &str) -> [u8; 20] {
+    calc_addr(deployer, salt.as_bytes())
+}
+
+/// calculates the address of a contract and formats it in one step.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - salt: arbitrary bytes; its keccak256 digest is used.
+///
+/// returns: the eip-55 checksummed address, with '0x'.
+pub fn calc_addr_checksummed(deployer: &[u8], salt: &[u8]) -> String {
+    checksum::to_checksum_address(&calc_addr(deployer, salt))
+}
+
+/// calculates the address of a contract for a salt given as hex, hashing
+/// the decoded bytes rather than the text, so "0x1234" is a two-byte salt.
+///
+/// use `calc_addr` to hash the salt text itself.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - salt_hex: salt bytes as hex, with or without '0x' or '0X'.
+///
+/// returns: a 20-byte contract address.
+pub fn calc_addr_hex_salt(
+    deployer: &[u8],
+    salt_hex: &str,
+) -> Result<[u8; 20], Create3GenerateSaltError> {
+    let digits = strip_hex_prefix(salt_hex);
+    let salt = hex::decode(digits)
+        .map_err(|_| Create3GenerateSaltError::SaltNotHexEncoded)?;
+    Ok(calc_addr(deployer, &salt))
+}
+
 /// calculates the address of a contract using deployer and salt bytes.
 ///
 /// arguments:
@@ -36,276 +156,3112 @@ pub fn calc_addr(deployer: &[u8], salt: &[u8]) -> [u8; 20] {
 ///
 /// returns: a 20-byte contract address.
 pub fn calc_addr_with_bytes(deployer: &[u8], salt: &[u8; 32]) -> [u8; 20] {
-    let mut bytes = Vec::new();
+    let proxy_bytes = calc_proxy_addr(deployer, salt);
+
+    // compute final address using proxy address and nonce 0x01.
+    let hash = Keccak256::digest(final_create_preimage(&proxy_bytes));
+
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// strips a leading '0x' or '0X' from hex input, if present.
+pub(crate) fn strip_hex_prefix(s: &str) -> &str {
+    s.strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s)
+}
+
+/// parses a 32-byte salt hash from hex, e.g. one printed by the cli.
+///
+/// arguments:
+/// - s: 64 hex characters, with or without '0x' or '0X'.
+///
+/// returns: the 32 salt bytes, ready for `calc_addr_with_bytes`.
+pub fn parse_salt_hash(s: &str) -> Result<[u8; 32], Create3GenerateSaltError> {
+    let digits = strip_hex_prefix(s);
+    if digits.len() != 64 {
+        return Err(Create3GenerateSaltError::InvalidSaltHash);
+    }
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(digits, &mut bytes)
+        .map_err(|_| Create3GenerateSaltError::InvalidSaltHash)?;
+    Ok(bytes)
+}
+
+/// formats a 32-byte salt as a solidity `bytes32` hex literal, ready to
+/// paste into a foundry script, e.g. `bytes32 salt = 0x3ac2...f1cb;`.
+///
+/// returns: '0x' followed by 64 lowercase hex characters.
+pub fn salt_hash_to_bytes32_literal(salt: &[u8; 32]) -> String {
+    format!("0x{}", hex::encode(salt))
+}
+
+/// calculates the addresses for many salts under one deployer, laying out
+/// the preimages once and only swapping the salt in for each one.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - salts: 32-byte values used as salts.
+///
+/// returns: the 20-byte contract address for each salt, in order.
+pub fn calc_addr_batch(deployer: &[u8], salts: &[[u8; 32]]) -> Vec<[u8; 20]> {
+    let mut preimage = proxy_create2_preimage(deployer, &[0; 32]);
+    let salt_at = 1 + deployer.len();
+    let mut preimage2 = final_create_preimage(&[0; 20]);
+    salts
+        .iter()
+        .map(|salt| {
+            preimage[salt_at..salt_at + 32].copy_from_slice(salt);
+            let hash = Keccak256::digest(&preimage);
+            preimage2[2..22].copy_from_slice(&hash[12..]);
+            let hash2 = Keccak256::digest(&preimage2);
+            let mut address = [0u8; 20];
+            address.copy_from_slice(&hash2[12..]);
+            address
+        })
+        .collect()
+}
+
+/// calculates the address of the intermediate proxy deployed with create2,
+/// which in turn deploys the contract.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - salt: 32-byte value used as the salt.
+///
+/// returns: the 20-byte proxy address.
+pub fn calc_proxy_addr(deployer: &[u8], salt: &[u8; 32]) -> [u8; 20] {
+    let hash = Keccak256::digest(proxy_create2_preimage(deployer, salt));
+    let mut proxy_bytes = [0u8; 20];
+    proxy_bytes.copy_from_slice(&hash[12..]);
+    proxy_bytes
+}
+
+/// calculates the address of a contract deployed directly with create2.
+///
+/// arguments:
+/// - deployer: address executing create2 (bytes).
+/// - salt: 32-byte value used as the salt.
+/// - init_code_hash: keccak256 digest of the contract's init code.
+///
+/// returns: keccak256(0xff ++ deployer ++ salt ++ init_code_hash)[12..].
+pub fn calc_create2_addr(
+    deployer: &[u8],
+    salt: &[u8; 32],
+    init_code_hash: &[u8; 32],
+) -> [u8; 20] {
+    let mut bytes = Vec::with_capacity(1 + deployer.len() + 64);
+    bytes.push(0xff);
+    bytes.extend_from_slice(deployer);
+    bytes.extend_from_slice(salt);
+    bytes.extend_from_slice(init_code_hash);
+    let hash = Keccak256::digest(&bytes);
+
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// calculates the address of a contract from a typed deployer, so its
+/// length is checked at compile time.
+///
+/// arguments:
+/// - deployer: 20-byte create3 deployer address.
+/// - salt: 32-byte value used as the salt.
+///
+/// returns: a 20-byte contract address.
+pub fn calc_addr_bytes20(deployer: &[u8; 20], salt: &[u8; 32]) -> [u8; 20] {
+    Create3::new(deployer).calc_addr_with_bytes(salt)
+}
+
+/// returns the bytes hashed to derive the proxy address, for checking the
+/// first step of the derivation independently.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - salt: 32-byte value used as the salt.
+///
+/// returns: `0xff ++ deployer ++ salt ++ keccak256(proxy bytecode)`.
+pub fn proxy_create2_preimage(deployer: &[u8], salt: &[u8; 32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(1 + deployer.len() + 64);
     bytes.push(0xff);
     bytes.extend_from_slice(deployer);
     bytes.extend_from_slice(salt);
     bytes.extend_from_slice(&KECCAK256_PROXY_CHILD_BYTECODE);
+    bytes
+}
 
-    let hash = Keccak256::digest(&bytes);
-    let mut proxy_bytes = [0u8; 20];
-    proxy_bytes.copy_from_slice(&hash[12..]);
+/// returns the bytes hashed to derive the final address from the proxy,
+/// for checking the second step of the derivation independently.
+///
+/// arguments:
+/// - proxy: 20-byte proxy address.
+///
+/// returns: `0xd6 ++ 0x94 ++ proxy ++ 0x01`, the rlp encoding of
+/// `[proxy, nonce 1]`.
+pub fn final_create_preimage(proxy: &[u8; 20]) -> Vec<u8> {
+    create_preimage(proxy, 1)
+}
 
-    // compute final address using proxy address and nonce 0x01.
-    let mut bytes2 = Vec::new();
-    bytes2.extend_from_slice(&[0xd6, 0x94]);
-    bytes2.extend(&proxy_bytes);
-    bytes2.push(0x01);
-    let hash2 = Keccak256::digest(&bytes2);
+/// rlp encodes `[sender, nonce]`, the preimage of a create address.
+fn create_preimage(sender: &[u8; 20], nonce: u64) -> Vec<u8> {
+    let nonce_bytes = nonce.to_be_bytes();
+    let nonce_bytes = &nonce_bytes[nonce.leading_zeros() as usize / 8..];
+    let mut encoded_nonce = Vec::with_capacity(9);
+    match nonce_bytes {
+        // zero is the empty string.
+        [] => encoded_nonce.push(0x80),
+        // small values encode as themselves.
+        [b] if *b < 0x80 => encoded_nonce.push(*b),
+        _ => {
+            encoded_nonce.push(0x80 + nonce_bytes.len() as u8);
+            encoded_nonce.extend_from_slice(nonce_bytes);
+        }
+    }
+    let mut bytes = Vec::with_capacity(31);
+    // the payload is at most 30 bytes, so the short list form always fits.
+    bytes.push(0xc0 + 21 + encoded_nonce.len() as u8);
+    bytes.push(0x94);
+    bytes.extend_from_slice(sender);
+    bytes.extend_from_slice(&encoded_nonce);
+    bytes
+}
 
+/// calculates the address of a contract deployed by the proxy at a given
+/// nonce, for flows where the proxy has already deployed before.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - salt: 32-byte value used as the salt.
+/// - nonce: proxy nonce at deployment; `calc_addr_with_bytes` assumes 1.
+///
+/// returns: a 20-byte contract address.
+pub fn calc_addr_with_nonce(
+    deployer: &[u8],
+    salt: &[u8; 32],
+    nonce: u64,
+) -> [u8; 20] {
+    let proxy = calc_proxy_addr(deployer, salt);
+    let hash = Keccak256::digest(create_preimage(&proxy, nonce));
     let mut address = [0u8; 20];
-    address.copy_from_slice(&hash2[12..]);
+    address.copy_from_slice(&hash[12..]);
     address
 }
 
-/// cleans and validates the prefix for salt generation.
+/// a salt found by a search, along with the address it produces.
+///
+/// with the `serde` feature, `salt_hash` and `address` serialize as
+/// 0x-prefixed hex strings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaltResult {
+    /// salt string that was hashed.
+    pub salt: String,
+    /// 32-byte keccak256 digest of the salt.
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub salt_hash: [u8; 32],
+    /// 20-byte contract address for the salt.
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub address: [u8; 20],
+    /// number of candidate salts evaluated to find this one, summed across
+    /// all threads.
+    pub attempts: u64,
+}
+
+impl core::fmt::Display for SaltResult {
+    /// formats the result on one line, as
+    /// `address=0x<checksum> salt="..." salt_hash=0x<64 hex>`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "address={} salt={:?} salt_hash=0x{}",
+            checksum::to_checksum_address(&self.address),
+            self.salt,
+            hex::encode(self.salt_hash)
+        )
+    }
+}
+
+/// ranks results from one or more searches by a score of their address,
+/// e.g. to pick the best match across prefix, suffix and zero-byte runs.
+///
+/// arguments:
+/// - results: results to rank, possibly from different searches.
+/// - scorer: scores an address; higher is better.
+///
+/// returns: the results from best to worst; ties keep their input order.
+pub fn rank_results(
+    results: &[SaltResult],
+    scorer: impl Fn(&[u8; 20]) -> i64,
+) -> Vec<&SaltResult> {
+    let mut ranked: Vec<(i64, &SaltResult)> = results
+        .iter()
+        .map(|result| (scorer(&result.address), result))
+        .collect();
+    ranked.sort_by_key(|&(score, _)| core::cmp::Reverse(score));
+    ranked.into_iter().map(|(_, result)| result).collect()
+}
+
+/// longest salt prefix accepted by the salt prefix searches, in bytes.
+///
+/// only the keccak256 digest of a salt reaches the create3 factory, so its
+/// length never changes the address. it does matter when the salt string
+/// itself is sent on-chain, e.g. to a factory that hashes a `string` salt,
+/// where every byte costs calldata gas.
+pub const MAX_SALT_PREFIX_LEN: usize = 256;
+
+/// checks that a salt prefix is at most `MAX_SALT_PREFIX_LEN` bytes long.
+#[cfg(feature = "std")]
+pub(crate) fn check_salt_prefix(
+    salt_prefix: &str,
+) -> Result<(), Create3GenerateSaltError> {
+    if salt_prefix.len() > MAX_SALT_PREFIX_LEN {
+        return Err(Create3GenerateSaltError::SaltPrefixTooLong);
+    }
+    Ok(())
+}
+
+/// checks that a deployer address is exactly 20 bytes long.
+pub(crate) fn check_deployer(
+    deployer: &[u8],
+) -> Result<(), Create3GenerateSaltError> {
+    if deployer.len() != 20 {
+        return Err(Create3GenerateSaltError::InvalidDeployerLength {
+            got: deployer.len(),
+        });
+    }
+    Ok(())
+}
+
+/// parses a decimal or 0x-prefixed hex integer into a big-endian 32-byte
+/// salt.
+fn parse_int_salt(
+    salt_int: &str,
+) -> Result<[u8; 32], Create3GenerateSaltError> {
+    let salt_int = salt_int.trim();
+    let mut salt = [0u8; 32];
+    if let Some(digits) = salt_int
+        .strip_prefix("0x")
+        .or_else(|| salt_int.strip_prefix("0X"))
+    {
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Create3GenerateSaltError::InvalidIntSalt);
+        }
+        let digits = digits.trim_start_matches('0');
+        if digits.len() > 64 {
+            return Err(Create3GenerateSaltError::IntSaltTooLarge);
+        }
+        let padded = format!("{:0>64}", digits);
+        salt.copy_from_slice(&hex::decode(padded).unwrap());
+        return Ok(salt);
+    }
+    if salt_int.is_empty() || !salt_int.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Create3GenerateSaltError::InvalidIntSalt);
+    }
+    for digit in salt_int.bytes().map(|c| c - b'0') {
+        // salt = salt * 10 + digit, from the least significant byte up.
+        let mut carry = digit as u16;
+        for byte in salt.iter_mut().rev() {
+            let value = *byte as u16 * 10 + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+        if carry != 0 {
+            return Err(Create3GenerateSaltError::IntSaltTooLarge);
+        }
+    }
+    Ok(salt)
+}
+
+/// calculates the address of a contract using a salt given as an integer.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - salt_int: decimal or 0x-prefixed hex integer (max 256 bits), used
+///   big-endian as the 32-byte salt.
+///
+/// returns: a 20-byte contract address.
+pub fn calc_addr_from_int_salt(
+    deployer: &[u8],
+    salt_int: &str,
+) -> Result<[u8; 20], Create3GenerateSaltError> {
+    Ok(calc_addr_with_bytes(deployer, &parse_int_salt(salt_int)?))
+}
+
+/// calculates the address of a contract for experimental factories that
+/// hash the salt twice.
+///
+/// this is not the standard derivation; use `calc_addr` unless the factory
+/// is known to double-hash.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - salt: arbitrary bytes; keccak256(keccak256(salt)) is used.
+///
+/// returns: a 20-byte contract address.
+pub fn calc_addr_double_hashed_salt(deployer: &[u8], salt: &[u8]) -> [u8; 20] {
+    calc_addr(deployer, &Keccak256::digest(salt))
+}
+
+/// derives the effective salt used by deployer-mixed factories.
+///
+/// returns: keccak256(deployer ++ user_salt).
+fn deployer_mixed_salt(deployer: &[u8], user_salt: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(deployer);
+    hasher.update(user_salt);
+    hasher.finalize().into()
+}
+
+/// calculates the address of a contract for factories that mix the
+/// deployer into the salt to prevent front-running.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - user_salt: arbitrary bytes; keccak256(deployer ++ user_salt) is used.
+///
+/// returns: a 20-byte contract address.
+pub fn calc_addr_deployer_mixed(deployer: &[u8], user_salt: &[u8]) -> [u8; 20] {
+    calc_addr_with_bytes(deployer, &deployer_mixed_salt(deployer, user_salt))
+}
+
+/// calculates the address of one version of a contract deployed from a
+/// shared base salt.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - base_salt: 32-byte salt shared by all versions.
+/// - version: version byte; keccak256(base_salt ++ [version]) is used.
+///
+/// returns: a 20-byte contract address.
+pub fn calc_addr_versioned(
+    deployer: &[u8],
+    base_salt: &[u8; 32],
+    version: u8,
+) -> [u8; 20] {
+    let mut salt = [0u8; 33];
+    salt[..32].copy_from_slice(base_salt);
+    salt[32] = version;
+    calc_addr(deployer, &salt)
+}
+
+/// calculates the addresses of versions `0..=max_version` of a contract
+/// deployed from a shared base salt (see `calc_addr_versioned`).
+///
+/// returns: addresses indexed by version.
+pub fn calc_addrs_versioned(
+    deployer: &[u8],
+    base_salt: &[u8; 32],
+    max_version: u8,
+) -> Vec<[u8; 20]> {
+    (0..=max_version)
+        .map(|version| calc_addr_versioned(deployer, base_salt, version))
+        .collect()
+}
+
+/// precomputed create3 context for a single deployer.
+///
+/// the `0xff || deployer || salt || proxy hash` preimage is laid out once, so
+/// each address calculation only overwrites the 32 salt bytes instead of
+/// allocating a fresh buffer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Create3 {
+    preimage: [u8; 85],
+}
+
+impl Create3 {
+    /// creates a context for the given deployer.
+    ///
+    /// arguments:
+    /// - deployer: 20-byte create3 deployer address.
+    pub fn new(deployer: &[u8; 20]) -> Self {
+        let mut preimage = [0u8; 85];
+        preimage[0] = 0xff;
+        preimage[1..21].copy_from_slice(deployer);
+        preimage[53..85].copy_from_slice(&KECCAK256_PROXY_CHILD_BYTECODE);
+        Create3 { preimage }
+    }
+
+    /// returns: the deployer address this context was built for.
+    pub fn deployer(&self) -> [u8; 20] {
+        self.preimage[1..21].try_into().unwrap()
+    }
+
+    /// calculates the address of a contract using an arbitrary salt.
+    ///
+    /// arguments:
+    /// - salt: arbitrary bytes; its keccak256 digest is used.
+    ///
+    /// returns: a 20-byte contract address.
+    pub fn calc_addr(&self, salt: &[u8]) -> [u8; 20] {
+        self.calc_addr_with_bytes(&Keccak256::digest(salt).into())
+    }
+
+    /// calculates the address of a contract using salt bytes.
+    ///
+    /// arguments:
+    /// - salt: 32-byte value used as the salt.
+    ///
+    /// returns: a 20-byte contract address.
+    pub fn calc_addr_with_bytes(&self, salt: &[u8; 32]) -> [u8; 20] {
+        let mut preimage = self.preimage;
+        preimage[21..53].copy_from_slice(salt);
+        let hash = Keccak256::digest(preimage);
+
+        // compute final address using proxy address and nonce 0x01.
+        let mut preimage2 = [0u8; 23];
+        preimage2[0..2].copy_from_slice(&[0xd6, 0x94]);
+        preimage2[2..22].copy_from_slice(&hash[12..]);
+        preimage2[22] = 0x01;
+        let hash2 = Keccak256::digest(preimage2);
+
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hash2[12..]);
+        address
+    }
+
+    /// iterates over salts whose addresses start with a prefix.
+    ///
+    /// arguments:
+    /// - prefix: desired address prefix (without '0x').
+    ///
+    /// returns: a lazy iterator yielding one match per `next()` call.
+    #[cfg(feature = "std")]
+    pub fn iter_matches(
+        &self,
+        prefix: &str,
+    ) -> Result<MatchIterator, Create3GenerateSaltError> {
+        let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+        Ok(MatchIterator::new(self.clone(), prefix))
+    }
+}
+
+impl From<[u8; 20]> for Create3 {
+    fn from(deployer: [u8; 20]) -> Self {
+        Create3::new(&deployer)
+    }
+}
+
+/// cleans and validates a prefix for matching against the address hex.
+///
+/// a leading '0x' or '0X' is stripped, so pasted prefixes like "0xdead"
+/// work; a bare "0" is kept as a single zero nibble.
 ///
 /// returns: a lowercase version of the prefix if valid.
-fn sanitize_prefix(prefix: &str) -> Result<String, Create3GenerateSaltError> {
-    let prefix = prefix.trim();
+pub(crate) fn sanitize_hex_prefix(
+    prefix: &str,
+) -> Result<String, Create3GenerateSaltError> {
+    Ok(validate_hex_prefix(prefix)?.to_lowercase())
+}
+
+/// cleans and validates a prefix for matching against the eip-55 form of
+/// the address, where the case of each letter matters.
+///
+/// like `sanitize_hex_prefix`, but the case of the prefix is kept, since
+/// lowercasing "DeAd" would search for a different checksummed address.
+/// each letter's case comes from its own bit of the address hash, so any
+/// casing of a hex prefix can appear in an eip-55 address; an empty
+/// prefix is rejected since it has no case to match.
+///
+/// returns: the prefix with any '0x' removed, in its original case.
+#[cfg(feature = "std")]
+pub(crate) fn sanitize_checksum_prefix(
+    prefix: &str,
+) -> Result<String, Create3GenerateSaltError> {
+    let prefix = validate_hex_prefix(prefix)?;
+    if prefix.is_empty() {
+        return Err(Create3GenerateSaltError::EmptyChecksumPrefix);
+    }
+    Ok(prefix.to_owned())
+}
+
+/// trims a prefix and strips any '0x' or '0X', then checks that what is
+/// left is at most 20 hex characters.
+fn validate_hex_prefix(prefix: &str) -> Result<&str, Create3GenerateSaltError> {
+    let prefix = strip_hex_prefix(prefix.trim());
     if prefix.len() > 20 {
         return Err(Create3GenerateSaltError::PrefixTooLong);
     } else if !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
         return Err(Create3GenerateSaltError::PrefixNotHexEncoded);
     }
-    Ok(prefix.to_lowercase())
+    Ok(prefix)
 }
 
-/// generates a random salt for a deployer and a given prefix.
+/// splits a sanitized hex prefix into one nibble value per character.
+pub(crate) fn to_nibbles(prefix: &str) -> Vec<u8> {
+    prefix
+        .chars()
+        .map(|c| c.to_digit(16).unwrap() as u8)
+        .collect()
+}
+
+/// checks whether an address starts with the given nibbles, without
+/// hex encoding the address.
+///
+/// arguments:
+/// - addr: 20-byte address to check.
+/// - prefix_nibbles: expected leading nibbles, one value (0-15) per element.
+pub(crate) fn matches_prefix(addr: &[u8; 20], prefix_nibbles: &[u8]) -> bool {
+    prefix_nibbles.iter().enumerate().all(|(i, &nibble)| {
+        let byte = addr[i / 2];
+        let actual = if i % 2 == 0 { byte >> 4 } else { byte & 0x0f };
+        actual == nibble
+    })
+}
+
+/// checks whether an address ends with the given nibbles.
+///
+/// arguments:
+/// - addr: 20-byte address to check.
+/// - suffix_nibbles: expected trailing nibbles, one value (0-15) per element.
+#[cfg(feature = "std")]
+pub(crate) fn matches_suffix(addr: &[u8; 20], suffix_nibbles: &[u8]) -> bool {
+    let Some(start) = 40usize.checked_sub(suffix_nibbles.len()) else {
+        return false;
+    };
+    suffix_nibbles.iter().enumerate().all(|(i, &nibble)| {
+        let pos = start + i;
+        let byte = addr[pos / 2];
+        let actual = if pos % 2 == 0 { byte >> 4 } else { byte & 0x0f };
+        actual == nibble
+    })
+}
+
+/// checks offline that a salt yields an address with the expected prefix,
+/// e.g. before broadcasting a deployment.
 ///
 /// arguments:
 /// - deployer: create3 deployer address (bytes).
-/// - prefix: desired address prefix (without '0x').
+/// - salt: arbitrary bytes; its keccak256 digest is used.
+/// - expected_prefix: address prefix the salt was mined for.
 ///
-/// returns: (salt string, 32-byte keccak256 digest of salt).
-pub fn generate_salt(
+/// returns: true if the address starts with the prefix; false otherwise,
+/// including when the prefix is not valid hex.
+pub fn verify_salt(
     deployer: &[u8],
-    prefix: &str,
-) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
-    let mut salt_bytes = [0; 32];
-    let mut salt: String;
-    let prefix = sanitize_prefix(prefix)?;
-    loop {
-        salt = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(10)
-            .map(char::from)
-            .collect();
-        let vanity_addr = calc_addr(deployer, salt.as_bytes());
-        let vanity_addr = hex::encode(&vanity_addr);
-        if vanity_addr.starts_with(&prefix) {
-            let salt_hex = hex::encode(Keccak256::digest(salt.clone()));
-            let salt_bytes_slice = hex::decode(&salt_hex).unwrap();
-            salt_bytes.copy_from_slice(&salt_bytes_slice);
-            break;
-        }
-    }
-    Ok((salt, salt_bytes))
+    salt: &[u8],
+    expected_prefix: &str,
+) -> bool {
+    sanitize_hex_prefix(expected_prefix).is_ok_and(|prefix| {
+        matches_prefix(&calc_addr(deployer, salt), &to_nibbles(&prefix))
+    })
 }
 
-/// generates a random salt using multiple threads for a given prefix.
+/// checks offline that a salt yields exactly the expected address.
 ///
 /// arguments:
 /// - deployer: create3 deployer address (bytes).
-/// - prefix: desired address prefix (without '0x').
-/// - thread_count: number of threads to spawn.
+/// - salt: arbitrary bytes; its keccak256 digest is used.
+/// - expected: 20-byte address the salt should produce.
 ///
-/// returns: (salt string, 32-byte keccak256 digest of salt).
-pub fn generate_salt_multithread(
+/// returns: true if the addresses are equal.
+pub fn verify_salt_address(
     deployer: &[u8],
-    prefix: &str,
-    thread_count: u8,
-) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
-    generate_salt_prefix_multithread(deployer, "", prefix, thread_count)
+    salt: &[u8],
+    expected: &[u8; 20],
+) -> bool {
+    calc_addr(deployer, salt) == *expected
 }
 
-/// generates a salt with a salt prefix for a given address prefix.
+/// returns: a fast rng for drawing salt candidates, seeded from entropy.
+///
+/// candidates only need to differ between attempts; a mined salt ends up
+/// public on chain anyway, so it is never a secret and a non-cryptographic
+/// rng is enough. drawing from `thread_rng` for every character was a
+/// measurable cost in the search loop.
+#[cfg(feature = "std")]
+pub(crate) fn search_rng() -> SmallRng {
+    SmallRng::from_entropy()
+}
+
+/// returns: a random alphanumeric string of the given length.
+#[cfg(feature = "std")]
+pub(crate) fn random_salt<R: Rng + ?Sized>(rng: &mut R, len: usize) -> String {
+    rng.sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+/// draws random alphanumeric salts until one yields a matching address.
 ///
 /// arguments:
 /// - deployer: create3 deployer address (bytes).
-/// - salt_prefix: string to append to the random salt.
-/// - prefix: desired address prefix (without '0x').
+/// - salt_prefix: string to prepend to the random salt.
+/// - salt_len: number of random characters in each salt.
+/// - rng: source of randomness for the salts.
+/// - is_match: predicate the resulting address must satisfy.
 ///
 /// returns: (salt string, 32-byte keccak256 digest of salt).
-pub fn generate_salt_prefix(
+#[cfg(feature = "std")]
+fn search_salt<R: Rng + ?Sized>(
     deployer: &[u8],
     salt_prefix: &str,
-    prefix: &str,
-) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
-    let mut salt_bytes = [0; 32];
-    let mut salt: String;
-    let prefix = sanitize_prefix(prefix)?;
+    salt_len: usize,
+    rng: &mut R,
+    is_match: impl Fn(&[u8; 20]) -> bool,
+) -> (String, [u8; 32]) {
     loop {
-        salt = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(7)
-            .map(char::from)
-            .collect();
-        salt = salt_prefix.to_owned() + &salt;
+        let salt = salt_prefix.to_owned() + &random_salt(rng, salt_len);
         let vanity_addr = calc_addr(deployer, salt.as_bytes());
-        let vanity_addr = hex::encode(&vanity_addr);
-        if vanity_addr.starts_with(&prefix) {
-            let salt_hex = hex::encode(Keccak256::digest(salt.clone()));
-            let salt_bytes_slice = hex::decode(&salt_hex).unwrap();
-            salt_bytes.copy_from_slice(&salt_bytes_slice);
-            break;
+        if is_match(&vanity_addr) {
+            let mut salt_bytes = [0; 32];
+            salt_bytes.copy_from_slice(Keccak256::digest(&salt).as_slice());
+            return (salt, salt_bytes);
         }
     }
-    Ok((salt, salt_bytes))
 }
 
-/// generates a salt with a salt prefix using multiple threads.
+/// generates a random salt for a deployer and a given prefix.
 ///
 /// arguments:
 /// - deployer: create3 deployer address (bytes).
-/// - salt_prefix: string to append to the random salt.
 /// - prefix: desired address prefix (without '0x').
-/// - thread_count: number of threads to spawn.
 ///
 /// returns: (salt string, 32-byte keccak256 digest of salt).
-pub fn generate_salt_prefix_multithread(
+#[cfg(feature = "std")]
+pub fn generate_salt(
     deployer: &[u8],
-    salt_prefix: &str,
     prefix: &str,
-    thread_count: u8,
 ) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
-    let lock: Arc<RwLock<(String, [u8; 32])>> =
-        Arc::new(RwLock::new(("".to_owned(), [0; 32])));
-    let mut threads: Vec<thread::JoinHandle<()>> = Vec::new();
-    let prefix = sanitize_prefix(prefix)?;
-    for _ in 0..thread_count {
-        let p = prefix.to_owned();
-        let d = deployer.to_owned();
-        let sp = salt_prefix.to_owned();
-        let lock = lock.clone();
-        let handle = thread::spawn(move || {
-            let mut salt: String;
-            let mut salt_bytes = [0; 32];
-            loop {
-                salt = rand::thread_rng()
-                    .sample_iter(&Alphanumeric)
-                    .take(7)
-                    .map(char::from)
-                    .collect();
-                salt = sp.to_owned() + &salt;
-                let vanity_addr = calc_addr(&d, salt.as_bytes());
-                let vanity_addr = hex::encode(&vanity_addr);
-                let Ok(read_lock) = lock.try_read() else {
-                    break;
+    generate_salt_with_rng(deployer, prefix, &mut search_rng())
+}
+
+/// generates a salt for a given prefix using the supplied rng, so a seeded
+/// rng makes the search reproducible.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - rng: source of randomness for candidate salts.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt).
+#[cfg(feature = "std")]
+pub fn generate_salt_with_rng<R: Rng + ?Sized>(
+    deployer: &[u8],
+    prefix: &str,
+    rng: &mut R,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(deployer)?;
+    Ok(search_salt(deployer, "", 10, rng, |addr| {
+        matches_prefix(addr, &prefix)
+    }))
+}
+
+/// generates a random salt for a given prefix, giving up after a fixed
+/// number of attempts.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - max_attempts: maximum number of salts to try.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt), or a
+/// `NotFound` error with the exact number of attempts made.
+#[cfg(feature = "std")]
+pub fn generate_salt_bounded(
+    deployer: &[u8],
+    prefix: &str,
+    max_attempts: u64,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(deployer)?;
+    let mut rng = search_rng();
+    for _ in 0..max_attempts {
+        let salt = random_salt(&mut rng, 10);
+        if matches_prefix(&calc_addr(deployer, salt.as_bytes()), &prefix) {
+            let salt_bytes = Keccak256::digest(&salt).into();
+            return Ok((salt, salt_bytes));
+        }
+    }
+    Err(Create3GenerateSaltError::NotFound {
+        attempts: max_attempts,
+    })
+}
+
+/// generates a random salt for a given prefix, drawing salt bytes from a
+/// custom alphabet.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - alphabet: bytes the salt may be made of.
+/// - salt_len: number of bytes in each salt.
+/// - printable_only: reject alphabets with bytes outside printable ascii,
+///   so the salt is safe to display and log.
+///
+/// returns: (salt bytes, 32-byte keccak256 digest of salt).
+#[cfg(feature = "std")]
+pub fn generate_salt_alphabet(
+    deployer: &[u8],
+    prefix: &str,
+    alphabet: &[u8],
+    salt_len: usize,
+    printable_only: bool,
+) -> Result<(Vec<u8>, [u8; 32]), Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(deployer)?;
+    if alphabet.is_empty() {
+        return Err(Create3GenerateSaltError::EmptyAlphabet);
+    }
+    if printable_only {
+        let bytes: Vec<u8> = alphabet
+            .iter()
+            .copied()
+            .filter(|b| !(b' '..=b'~').contains(b))
+            .collect();
+        if !bytes.is_empty() {
+            return Err(Create3GenerateSaltError::NonPrintableAlphabet {
+                bytes,
+            });
+        }
+    }
+    let mut rng = search_rng();
+    loop {
+        let salt: Vec<u8> = (0..salt_len)
+            .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+            .collect();
+        if matches_prefix(&calc_addr(deployer, &salt), &prefix) {
+            let salt_bytes = Keccak256::digest(&salt).into();
+            return Ok((salt, salt_bytes));
+        }
+    }
+}
+
+/// characters a generated salt may be made of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Charset {
+    /// a-z, A-Z and 0-9.
+    Alphanumeric,
+    /// 0-9 and a-f.
+    HexLower,
+    /// 0-9.
+    Numeric,
+}
+
+impl Charset {
+    /// returns: the characters in the set, as ascii bytes.
+    pub fn alphabet(&self) -> &'static [u8] {
+        match self {
+            Charset::Alphanumeric => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789"
+            }
+            Charset::HexLower => b"0123456789abcdef",
+            Charset::Numeric => b"0123456789",
+        }
+    }
+}
+
+/// generates a random salt for a given prefix with a chosen length and
+/// charset, e.g. short salts for cheaper on-chain storage.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - salt_len: number of characters in each salt.
+/// - charset: characters the salt may be made of.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt), or a
+/// `SaltSpaceTooSmall` error if there are fewer possible salts than the
+/// prefix needs on average.
+#[cfg(feature = "std")]
+pub fn generate_salt_custom(
+    deployer: &[u8],
+    prefix: &str,
+    salt_len: usize,
+    charset: Charset,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    let salt_space = (charset.alphabet().len() as f64).powi(salt_len as i32);
+    if salt_space < difficulty::expected_attempts(prefix)? {
+        return Err(Create3GenerateSaltError::SaltSpaceTooSmall);
+    }
+    let (salt, salt_bytes) = generate_salt_alphabet(
+        deployer,
+        prefix,
+        charset.alphabet(),
+        salt_len,
+        true,
+    )?;
+    // every charset is ascii, so the salt is valid utf-8.
+    Ok((String::from_utf8(salt).unwrap(), salt_bytes))
+}
+
+/// rechecks precomputed salts against a new deployer, e.g. when migrating
+/// to a new factory.
+///
+/// arguments:
+/// - old_deployer: create3 deployer address (bytes) the salts were mined
+///   for.
+/// - new_deployer: create3 deployer address (bytes) the salts will be used
+///   with.
+/// - salts: 32-byte salts to recheck.
+/// - prefix: address prefix the salts must still produce (without '0x').
+///
+/// returns: for each salt, whether its address starts with the prefix under
+/// both deployers; a salt that didn't match under the old deployer can't
+/// still match and is reported as false.
+pub fn revalidate_batch(
+    old_deployer: &[u8],
+    new_deployer: &[u8],
+    salts: &[[u8; 32]],
+    prefix: &str,
+) -> Result<Vec<bool>, Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(old_deployer)?;
+    check_deployer(new_deployer)?;
+    Ok(salts
+        .iter()
+        .map(|salt| {
+            matches_prefix(&calc_addr_with_bytes(old_deployer, salt), &prefix)
+                && matches_prefix(
+                    &calc_addr_with_bytes(new_deployer, salt),
+                    &prefix,
+                )
+        })
+        .collect())
+}
+
+/// searches random 32-byte salts for one whose address is exactly one of
+/// the targets.
+///
+/// this is a preimage search, so it is only feasible for tiny target sets
+/// with a generous budget.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - targets: 20-byte addresses that would be acceptable.
+/// - max_attempts: maximum number of salts to try.
+///
+/// returns: (32-byte salt, address hit), or None once the budget runs out.
+#[cfg(feature = "std")]
+pub fn generate_salt_for_any_address(
+    deployer: &[u8],
+    targets: &[[u8; 20]],
+    max_attempts: u64,
+) -> Option<([u8; 32], [u8; 20])> {
+    search_any_address(deployer, targets, max_attempts, &mut search_rng())
+}
+
+/// `generate_salt_for_any_address` with a caller supplied rng.
+#[cfg(feature = "std")]
+fn search_any_address<R: Rng + ?Sized>(
+    deployer: &[u8],
+    targets: &[[u8; 20]],
+    max_attempts: u64,
+    rng: &mut R,
+) -> Option<([u8; 32], [u8; 20])> {
+    let targets: HashSet<&[u8; 20]> = targets.iter().collect();
+    if targets.is_empty() {
+        return None;
+    }
+    (0..max_attempts).find_map(|_| {
+        let salt: [u8; 32] = rng.gen();
+        let address = calc_addr_with_bytes(deployer, &salt);
+        targets.contains(&address).then_some((salt, address))
+    })
+}
+
+/// resolves a requested thread count, treating 0 as every available core
+/// and capping larger requests at the core count.
+#[cfg(feature = "std")]
+pub(crate) fn resolve_thread_count(thread_count: usize) -> usize {
+    let cores = thread::available_parallelism().map_or(1, |n| n.get());
+    match thread_count {
+        0 => cores,
+        n => n.min(cores),
+    }
+}
+
+/// checks whether every byte of an address is the same value, as in the
+/// all-zero or all-0xff addresses.
+///
+/// arguments:
+/// - addr: 20-byte address to check.
+///
+/// returns: true if the address is made up of a single repeated byte.
+pub fn is_degenerate_addr(addr: &[u8; 20]) -> bool {
+    addr.iter().all(|&b| b == addr[0])
+}
+
+/// generates a random salt for a given prefix whose address is not
+/// degenerate (see `is_degenerate_addr`).
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt).
+#[cfg(feature = "std")]
+pub fn generate_salt_non_degenerate(
+    deployer: &[u8],
+    prefix: &str,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(deployer)?;
+    Ok(search_salt(deployer, "", 10, &mut search_rng(), |addr| {
+        matches_prefix(addr, &prefix) && !is_degenerate_addr(addr)
+    }))
+}
+
+/// generates a random salt for a given prefix under the deployer-mixed
+/// scheme (see `calc_addr_deployer_mixed`).
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+///
+/// returns: (salt string, 32-byte keccak256(deployer ++ salt)).
+#[cfg(feature = "std")]
+pub fn generate_salt_deployer_mixed(
+    deployer: &[u8],
+    prefix: &str,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(deployer)?;
+    let mut rng = search_rng();
+    loop {
+        let salt = random_salt(&mut rng, 10);
+        let mixed_salt = deployer_mixed_salt(deployer, salt.as_bytes());
+        let vanity_addr = calc_addr_with_bytes(deployer, &mixed_salt);
+        if matches_prefix(&vanity_addr, &prefix) {
+            return Ok((salt, mixed_salt));
+        }
+    }
+}
+
+/// encodes a search counter as the 32-byte salt `generate_salt_counter`
+/// uses.
+///
+/// the counter is written big-endian into the last 8 bytes and the first
+/// 24 bytes are zero, so the salt reads as the counter when taken as a
+/// uint256, e.g. `bytes32(uint256(counter))` in solidity. the salt is used
+/// as is, without hashing.
+///
+/// arguments:
+/// - counter: search counter.
+///
+/// returns: the 32-byte salt.
+pub fn salt_from_counter(counter: u64) -> [u8; 32] {
+    let mut salt = [0u8; 32];
+    salt[24..].copy_from_slice(&counter.to_be_bytes());
+    salt
+}
+
+/// generates a salt for a given prefix by counting up from `start`, so an
+/// interrupted search can be resumed deterministically.
+///
+/// each counter is turned into a salt with `salt_from_counter`.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - start: first counter value to try.
+///
+/// returns: (matching counter, 32-byte salt); resume from counter + 1.
+pub fn generate_salt_counter(
+    deployer: &[u8],
+    prefix: &str,
+    start: u64,
+) -> Result<(u64, [u8; 32]), Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(deployer)?;
+    for counter in start..=u64::MAX {
+        let salt = salt_from_counter(counter);
+        if matches_prefix(&calc_addr_with_bytes(deployer, &salt), &prefix) {
+            return Ok((counter, salt));
+        }
+    }
+    Err(Create3GenerateSaltError::CounterExhausted)
+}
+
+/// generates a random salt using multiple threads for a given prefix.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt).
+#[cfg(feature = "std")]
+pub fn generate_salt_multithread(
+    deployer: &[u8],
+    prefix: &str,
+    thread_count: usize,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    generate_salt_prefix_multithread(deployer, "", prefix, thread_count)
+}
+
+/// generates a salt with a salt prefix for a given address prefix.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - salt_prefix: string to put in front of the random salt (max 256
+///   bytes).
+/// - prefix: desired address prefix (without '0x').
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt).
+#[cfg(feature = "std")]
+pub fn generate_salt_prefix(
+    deployer: &[u8],
+    salt_prefix: &str,
+    prefix: &str,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(deployer)?;
+    check_salt_prefix(salt_prefix)?;
+    Ok(search_salt(
+        deployer,
+        salt_prefix,
+        7,
+        &mut search_rng(),
+        |addr| matches_prefix(addr, &prefix),
+    ))
+}
+
+/// generates a salt with a salt prefix using multiple threads.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - salt_prefix: string to put in front of the random salt (max 256
+///   bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt).
+#[cfg(feature = "std")]
+pub fn generate_salt_prefix_multithread(
+    deployer: &[u8],
+    salt_prefix: &str,
+    prefix: &str,
+    thread_count: usize,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    generate_salt_prefix_cancellable(
+        deployer,
+        salt_prefix,
+        prefix,
+        thread_count,
+        Arc::new(AtomicBool::new(false)),
+        &AtomicU64::new(0),
+    )
+}
+
+/// generates a salt with a salt prefix using multiple threads, counting
+/// every salt tried in a shared counter.
+///
+/// workers add to `attempts` in batches as they go, so another thread can
+/// read it while the search runs, e.g. to report progress.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - salt_prefix: string to put in front of the random salt (max 256
+///   bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+/// - attempts: counter the workers add their attempts to.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt, final value of
+/// the counter).
+#[cfg(feature = "std")]
+pub fn generate_salt_prefix_multithread_counted(
+    deployer: &[u8],
+    salt_prefix: &str,
+    prefix: &str,
+    thread_count: usize,
+    attempts: Arc<AtomicU64>,
+) -> Result<(String, [u8; 32], u64), Create3GenerateSaltError> {
+    let (salt, salt_hash) = generate_salt_prefix_cancellable(
+        deployer,
+        salt_prefix,
+        prefix,
+        thread_count,
+        Arc::new(AtomicBool::new(false)),
+        &attempts,
+    )?;
+    Ok((salt, salt_hash, attempts.load(Ordering::Relaxed)))
+}
+
+/// generates a random salt using multiple threads for a given suffix.
+///
+/// the first worker to find a match wins and the others stop.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - suffix: desired address suffix (without '0x').
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt).
+#[cfg(feature = "std")]
+pub fn generate_salt_suffix_multithread(
+    deployer: &[u8],
+    suffix: &str,
+    thread_count: usize,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    let suffix = to_nibbles(&sanitize_hex_prefix(suffix)?);
+    generate_salt_predicate(deployer, thread_count, |address| {
+        matches_suffix(address, &suffix)
+    })
+}
+
+/// generates a salt whose address, written in uppercase hex as some
+/// explorers show it, starts with an uppercase prefix such as "DEAD".
+///
+/// unlike the checksum mode, the case of the address letters doesn't
+/// depend on a hash; a lowercase letter in the prefix is rejected since
+/// uppercase hex never contains one.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired uppercase address prefix (max 20 characters, with or
+///   without '0x').
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt).
+#[cfg(feature = "std")]
+pub fn generate_salt_uppercase_hex(
+    deployer: &[u8],
+    prefix: &str,
+    thread_count: usize,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    let prefix = validate_hex_prefix(prefix)?;
+    if prefix.chars().any(|c| c.is_ascii_lowercase()) {
+        return Err(Create3GenerateSaltError::PrefixNotUppercase);
+    }
+    // matching nibbles is the same as comparing against
+    // `hex::encode_upper(address)`, without encoding every candidate.
+    let prefix = to_nibbles(prefix);
+    generate_salt_predicate(deployer, thread_count, |address| {
+        matches_prefix(address, &prefix)
+    })
+}
+
+/// generates a salt whose address matches a nibble pattern, using
+/// multiple threads.
+///
+/// the pattern is anchored at the start of the address and holds hex
+/// digits, which must match, and '?' wildcards, which match any nibble;
+/// e.g. "de?d??ef". a leading '0x' is ignored.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - pattern: hex digits and '?' wildcards (max 40 characters).
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt).
+#[cfg(feature = "std")]
+pub fn generate_salt_pattern(
+    deployer: &[u8],
+    pattern: &str,
+    thread_count: usize,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    let constraints = parse_pattern(pattern)?;
+    check_deployer(deployer)?;
+    search_parallel(
+        deployer,
+        "",
+        thread_count,
+        SearchControl {
+            stop: &AtomicBool::new(false),
+            max_attempts: u64::MAX,
+            attempts: &AtomicU64::new(0),
+            progress: None,
+        },
+        |_, address| matches_pattern(address, &constraints),
+    )
+}
+
+/// generates a salt whose address passes a custom check, using multiple
+/// threads.
+///
+/// the most general search; the prefix, suffix and mask variants are all
+/// special cases of it.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+/// - predicate: returns true for an acceptable 20-byte address; called
+///   from every worker thread.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt).
+#[cfg(feature = "std")]
+pub fn generate_salt_predicate(
+    deployer: &[u8],
+    thread_count: usize,
+    predicate: impl Fn(&[u8; 20]) -> bool + Send + Sync,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    check_deployer(deployer)?;
+    search_parallel(
+        deployer,
+        "",
+        thread_count,
+        SearchControl {
+            stop: &AtomicBool::new(false),
+            max_attempts: u64::MAX,
+            attempts: &AtomicU64::new(0),
+            progress: None,
+        },
+        |_, address| predicate(address),
+    )
+}
+
+/// generates a salt whose address matches a target under a byte mask,
+/// using multiple threads.
+///
+/// an address matches when `addr[i] & mask[i] == target[i] & mask[i]` for
+/// every byte, so prefixes, suffixes and fixed bytes anywhere in the
+/// address can all be expressed; target bits outside the mask are ignored.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - mask: bits of the address that are constrained.
+/// - target: expected values of the masked bits.
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt).
+#[cfg(feature = "std")]
+pub fn generate_salt_masked(
+    deployer: &[u8],
+    mask: &[u8; 20],
+    target: &[u8; 20],
+    thread_count: usize,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    generate_salt_predicate(deployer, thread_count, |address| {
+        matches_masked(address, mask, target)
+    })
+}
+
+/// generates a salt whose address contains any of several hex strings
+/// anywhere in it, using multiple threads.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - needles: hex strings to look for (max 20 characters each).
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt, index of the
+/// first needle the address contains).
+#[cfg(feature = "std")]
+pub fn generate_salt_contains_any(
+    deployer: &[u8],
+    needles: &[&str],
+    thread_count: usize,
+) -> Result<(String, [u8; 32], usize), Create3GenerateSaltError> {
+    if needles.is_empty() {
+        return Err(Create3GenerateSaltError::NoNeedles);
+    }
+    let needles = needles
+        .iter()
+        .map(|needle| Ok(to_nibbles(&sanitize_hex_prefix(needle)?)))
+        .collect::<Result<Vec<_>, Create3GenerateSaltError>>()?;
+    check_deployer(deployer)?;
+    let matching_needle = |address: &[u8; 20]| {
+        let nibbles = address_nibbles(address);
+        needles
+            .iter()
+            .position(|needle| contains_nibbles(&nibbles, needle))
+    };
+    let (salt, salt_hash) = search_parallel(
+        deployer,
+        "",
+        thread_count,
+        SearchControl {
+            stop: &AtomicBool::new(false),
+            max_attempts: u64::MAX,
+            attempts: &AtomicU64::new(0),
+            progress: None,
+        },
+        |_, address| matching_needle(address).is_some(),
+    )?;
+    let address = calc_addr_with_bytes(deployer, &salt_hash);
+    // the search only returns salts for which some needle matched.
+    let needle = matching_needle(&address).unwrap_or_default();
+    Ok((salt, salt_hash, needle))
+}
+
+/// spells a word in hex, swapping letters for look-alike digits
+/// (o→0, i→1, z→2, s→5, g→6, t→7); a-f and digits are kept as is.
+///
+/// returns: the lowercase hex spelling, or None if a character has no
+/// look-alike, e.g. the 'h' and 'l' in "hello".
+pub fn leetify(word: &str) -> Option<String> {
+    word.chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            c @ ('0'..='9' | 'a'..='f') => Some(c),
+            'o' => Some('0'),
+            'i' => Some('1'),
+            'z' => Some('2'),
+            's' => Some('5'),
+            'g' => Some('6'),
+            't' => Some('7'),
+            _ => None,
+        })
+        .collect()
+}
+
+/// generates a salt whose address spells a word anywhere in it, using
+/// multiple threads; the word is first turned into hex with `leetify`.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - word: word to look for, e.g. "b00st" or "boost" (max 20 characters).
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt).
+#[cfg(feature = "std")]
+pub fn generate_salt_word(
+    deployer: &[u8],
+    word: &str,
+    thread_count: usize,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    let hex = leetify(word).ok_or(Create3GenerateSaltError::UnmappableWord)?;
+    let (salt, salt_hash, _) =
+        generate_salt_contains_any(deployer, &[&hex], thread_count)?;
+    Ok((salt, salt_hash))
+}
+
+/// splits an address into its 40 nibbles, high nibble first.
+#[cfg(feature = "std")]
+pub(crate) fn address_nibbles(addr: &[u8; 20]) -> [u8; 40] {
+    let mut nibbles = [0u8; 40];
+    for (i, b) in addr.iter().enumerate() {
+        nibbles[2 * i] = b >> 4;
+        nibbles[2 * i + 1] = b & 0x0f;
+    }
+    nibbles
+}
+
+/// checks whether a run of nibbles appears anywhere in another.
+#[cfg(feature = "std")]
+pub(crate) fn contains_nibbles(haystack: &[u8], needle: &[u8]) -> bool {
+    needle.is_empty()
+        || haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+}
+
+/// checks whether the masked bits of an address equal those of a target.
+pub fn matches_masked(
+    addr: &[u8; 20],
+    mask: &[u8; 20],
+    target: &[u8; 20],
+) -> bool {
+    addr.iter()
+        .zip(mask)
+        .zip(target)
+        .all(|((a, m), t)| a & m == t & m)
+}
+
+/// parses a nibble pattern into the positions it fixes.
+///
+/// returns: (nibble index, expected nibble) for every non-wildcard
+/// character.
+#[cfg(feature = "std")]
+pub(crate) fn parse_pattern(
+    pattern: &str,
+) -> Result<Vec<(usize, u8)>, Create3GenerateSaltError> {
+    let pattern = strip_hex_prefix(pattern);
+    if pattern.len() > 40 {
+        return Err(Create3GenerateSaltError::PrefixTooLong);
+    }
+    pattern
+        .chars()
+        .enumerate()
+        .filter(|&(_, c)| c != '?')
+        .map(|(i, c)| {
+            c.to_digit(16)
+                .map(|nibble| (i, nibble as u8))
+                .ok_or(Create3GenerateSaltError::InvalidPatternCharacter)
+        })
+        .collect()
+}
+
+/// checks an address against the constraints from `parse_pattern`.
+#[cfg(feature = "std")]
+pub(crate) fn matches_pattern(
+    addr: &[u8; 20],
+    constraints: &[(usize, u8)],
+) -> bool {
+    constraints.iter().all(|&(i, nibble)| {
+        let byte = addr[i / 2];
+        let actual = if i % 2 == 0 { byte >> 4 } else { byte & 0x0f };
+        actual == nibble
+    })
+}
+
+/// generates a random salt using multiple threads for a given prefix,
+/// stopping early once `stop` is set.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+/// - stop: flag which cancels the search once set, e.g. from another thread.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt), or a
+/// `Cancelled` error if stopped before a match was found.
+#[cfg(feature = "std")]
+pub fn generate_salt_cancellable(
+    deployer: &[u8],
+    prefix: &str,
+    thread_count: usize,
+    stop: Arc<AtomicBool>,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    generate_salt_prefix_cancellable(
+        deployer,
+        "",
+        prefix,
+        thread_count,
+        stop,
+        &AtomicU64::new(0),
+    )
+}
+
+/// multithreaded search behind the salt prefix and cancellable variants.
+#[cfg(feature = "std")]
+fn generate_salt_prefix_cancellable(
+    deployer: &[u8],
+    salt_prefix: &str,
+    prefix: &str,
+    thread_count: usize,
+    stop: Arc<AtomicBool>,
+    attempts: &AtomicU64,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    search_salt_parallel(
+        deployer,
+        salt_prefix,
+        prefix,
+        thread_count,
+        &stop,
+        u64::MAX,
+        attempts,
+    )
+}
+
+/// runs the multithreaded search for at most `max_attempts` salts.
+///
+/// returns: the match, `Cancelled` if `stop` was raised first, or
+/// `NotFound` once every attempt has been used without a match.
+#[cfg(feature = "std")]
+fn search_salt_parallel(
+    deployer: &[u8],
+    salt_prefix: &str,
+    prefix: &str,
+    thread_count: usize,
+    stop: &AtomicBool,
+    max_attempts: u64,
+    attempts: &AtomicU64,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(deployer)?;
+    check_salt_prefix(salt_prefix)?;
+    let (salt, salt_hash) = search_parallel(
+        deployer,
+        salt_prefix,
+        thread_count,
+        SearchControl {
+            stop,
+            max_attempts,
+            attempts,
+            progress: None,
+        },
+        |_, address| matches_prefix(address, &prefix),
+    )?;
+    // re-derive the address from what is handed back, so a bug in how the
+    // workers store their match can't surface as a wrong salt.
+    let verified = verify_prefix_match(deployer, &salt, &salt_hash, &prefix);
+    debug_assert!(verified, "search returned a salt that doesn't match");
+    if !verified {
+        return Err(Create3GenerateSaltError::NotFound {
+            attempts: attempts.load(Ordering::Relaxed),
+        });
+    }
+    Ok((salt, salt_hash))
+}
+
+/// checks that a salt hashes to `salt_hash` and that the address for that
+/// hash starts with the prefix.
+#[cfg(feature = "std")]
+fn verify_prefix_match(
+    deployer: &[u8],
+    salt: &str,
+    salt_hash: &[u8; 32],
+    prefix_nibbles: &[u8],
+) -> bool {
+    Keccak256::digest(salt).as_slice() == salt_hash
+        && matches_prefix(
+            &calc_addr_with_bytes(deployer, salt_hash),
+            prefix_nibbles,
+        )
+}
+
+/// limits and hooks of a `search_parallel` run.
+#[cfg(feature = "std")]
+pub(crate) struct SearchControl<'a> {
+    /// cancels the search once raised.
+    pub(crate) stop: &'a AtomicBool,
+    /// salts to try at most, across all threads.
+    pub(crate) max_attempts: u64,
+    /// salts tried so far, across all threads.
+    pub(crate) attempts: &'a AtomicU64,
+    /// callback run from the calling thread every interval while the
+    /// search runs.
+    pub(crate) progress: Option<(Duration, &'a mut dyn FnMut(MiningStats))>,
+}
+
+/// runs the multithreaded search for salts whose address passes `matches`,
+/// which is given the salt and its address.
+///
+/// every salt tried is added to `attempts` once per claimed chunk so the
+/// workers don't contend on it, and a worker adds the rest of its chunk
+/// when it stops, so the count is exact once the search returns.
+///
+/// progress is never taken once a match has been stored or the search was
+/// cancelled.
+#[cfg(feature = "std")]
+pub(crate) fn search_parallel(
+    deployer: &[u8],
+    salt_prefix: &str,
+    thread_count: usize,
+    control: SearchControl,
+    matches: impl Fn(&str, &[u8; 20]) -> bool + Sync,
+) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
+    let SearchControl {
+        stop,
+        max_attempts,
+        attempts,
+        progress,
+    } = control;
+    let found = AtomicBool::new(false);
+    let claimed = AtomicU64::new(0);
+    let result: Mutex<Option<(String, [u8; 32])>> = Mutex::new(None);
+    // scoped workers borrow the deployer, prefixes and flags instead of
+    // each owning a copy.
+    let joined: Vec<thread::Result<()>> = thread::scope(|s| {
+        let workers: Vec<_> = (0..resolve_thread_count(thread_count))
+            .map(|_| {
+                s.spawn(|| {
+                    let _guard = StopOnPanic(&found);
+                    worker_panic_hook(deployer);
+                    let mut rng = search_rng();
+                    let done = || {
+                        found.load(Ordering::Relaxed)
+                            || stop.load(Ordering::Relaxed)
+                    };
+                    while !done() {
+                        let start =
+                            claimed.fetch_add(SEARCH_CHUNK, Ordering::Relaxed);
+                        if start >= max_attempts {
+                            break;
+                        }
+                        let mut tried = 0;
+                        for _ in 0..SEARCH_CHUNK.min(max_attempts - start) {
+                            if done() {
+                                break;
+                            }
+                            let salt = salt_prefix.to_owned()
+                                + &random_salt(&mut rng, 7);
+                            let address = calc_addr(deployer, salt.as_bytes());
+                            tried += 1;
+                            if !matches(&salt, &address) {
+                                continue;
+                            }
+                            // storing under the lock keeps a progress
+                            // report from going out after the match.
+                            if let Ok(mut result) = result.lock() {
+                                if result.is_none() {
+                                    let salt_hash = Keccak256::digest(&salt);
+                                    *result = Some((salt, salt_hash.into()));
+                                }
+                            }
+                            found.store(true, Ordering::Relaxed);
+                            break;
+                        }
+                        attempts.fetch_add(tried, Ordering::Relaxed);
+                    }
+                })
+            })
+            .collect();
+        if let Some((interval, on_progress)) = progress {
+            let start = Instant::now();
+            let mut next_report = start.checked_add(interval);
+            while !workers.iter().all(|worker| worker.is_finished()) {
+                thread::sleep(PROGRESS_POLL_INTERVAL);
+                let Some(report_at) = next_report else {
+                    continue;
                 };
-                if read_lock.0.len() > 0 {
-                    break;
-                }
-                if !vanity_addr.starts_with(&p) {
+                if Instant::now() < report_at {
                     continue;
                 }
-                drop(read_lock);
-                let mut write_lock = lock.write().unwrap();
-                let salt_hex = hex::encode(Keccak256::digest(salt.clone()));
-                let salt_bytes_slice = hex::decode(&salt_hex).unwrap();
-                salt_bytes.copy_from_slice(&salt_bytes_slice);
-                *write_lock = (salt, salt_bytes);
-                drop(write_lock);
-                break;
+                next_report = report_at.checked_add(interval);
+                // the stats are taken under the lock, so none are taken
+                // after a match was stored, but the callback runs without
+                // it so a slow one doesn't hold up a worker with a match.
+                let stats = {
+                    let Ok(result) = result.lock() else {
+                        break;
+                    };
+                    if result.is_some() || stop.load(Ordering::Relaxed) {
+                        continue;
+                    }
+                    let elapsed = start.elapsed();
+                    let attempts = attempts.load(Ordering::Relaxed);
+                    MiningStats {
+                        attempts,
+                        elapsed,
+                        hashes_per_sec: attempts as f64 / elapsed.as_secs_f64(),
+                    }
+                };
+                on_progress(stats);
+            }
+        }
+        // joining every worker here keeps a panic from escaping the scope.
+        workers.into_iter().map(|worker| worker.join()).collect()
+    });
+    if joined.iter().any(|worker| worker.is_err()) {
+        return Err(Create3GenerateSaltError::WorkerPanicked);
+    }
+    let result = result
+        .into_inner()
+        .map_err(|_| Create3GenerateSaltError::WorkerPanicked)?;
+    match result {
+        Some(found) => Ok(found),
+        None if stop.load(Ordering::Relaxed) => {
+            Err(Create3GenerateSaltError::Cancelled)
+        }
+        // never hand back an empty salt that doesn't match the prefix.
+        None => Err(Create3GenerateSaltError::NotFound {
+            attempts: max_attempts,
+        }),
+    }
+}
+
+/// raises a flag if the owning worker thread unwinds, so its peers stop
+/// instead of searching forever for a result nobody will collect.
+#[cfg(feature = "std")]
+pub(crate) struct StopOnPanic<'a>(pub(crate) &'a AtomicBool);
+
+#[cfg(feature = "std")]
+impl Drop for StopOnPanic<'_> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// deployer which makes mining workers panic in tests.
+#[cfg(all(test, feature = "std"))]
+pub(crate) const PANICKING_DEPLOYER: [u8; 20] = [0xee; 20];
+
+/// test-only hook that panics in a worker mining for
+/// `PANICKING_DEPLOYER`, to exercise the `WorkerPanicked` path; a no-op
+/// outside tests.
+#[cfg(feature = "std")]
+#[inline(always)]
+pub(crate) fn worker_panic_hook(_deployer: &[u8]) {
+    #[cfg(test)]
+    if _deployer == PANICKING_DEPLOYER {
+        panic!("injected worker panic");
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{
+        calc_addr, calc_addr_batch, calc_addr_bytes20, calc_addr_checksummed,
+        calc_addr_deployer_mixed, calc_addr_double_hashed_salt,
+        calc_addr_from_int_salt, calc_addr_hex_salt, calc_addr_str,
+        calc_addr_versioned, calc_addr_with_bytes, calc_addr_with_nonce,
+        calc_addrs_versioned, calc_create2_addr, calc_proxy_addr,
+        checksum::to_checksum_address, create_preimage, final_create_preimage,
+        generate_salt, generate_salt_alphabet, generate_salt_bounded,
+        generate_salt_cancellable, generate_salt_contains_any,
+        generate_salt_counter, generate_salt_custom,
+        generate_salt_deployer_mixed, generate_salt_for_any_address,
+        generate_salt_masked, generate_salt_multithread,
+        generate_salt_non_degenerate, generate_salt_pattern,
+        generate_salt_predicate, generate_salt_prefix,
+        generate_salt_prefix_multithread,
+        generate_salt_prefix_multithread_counted,
+        generate_salt_suffix_multithread, generate_salt_uppercase_hex,
+        generate_salt_with_rng, generate_salt_word, is_degenerate_addr,
+        leetify, matches_masked, matches_pattern, matches_prefix,
+        matches_suffix, miner::Create3Miner, parse_pattern, parse_salt_hash,
+        proxy_create2_preimage, random_salt, rank_results,
+        resolve_thread_count, revalidate_batch, salt_from_counter,
+        salt_hash_to_bytes32_literal, sanitize_checksum_prefix,
+        sanitize_hex_prefix, search_any_address, search_parallel, search_rng,
+        search_salt_parallel, to_nibbles, verify_prefix_match, verify_salt,
+        verify_salt_address, Charset, Create3, Create3GenerateSaltError,
+        SaltResult, SearchControl, MAX_SALT_PREFIX_LEN, PANICKING_DEPLOYER,
+    };
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use sha3::{Digest, Keccak256};
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn should_calculate_correctly_with_given_salt_string() {
+        let deployer: Vec<u8> =
+            hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap();
+        let correct_answers: Vec<(&str, &str)> = vec![
+            ("a", "BFf47440D3A5E59714F1D995F8b105E2a04AB46A"),
+            ("b", "7E10Ca8fa1c8e1528601Fea82F51646182f835b8"),
+            ("c", "70b556548FF0161082fB751d5E372eFa0133805C"),
+            (
+                "Some say the world will end in fire, Some say in ice. From what \
+I’ve tasted of desire I hold with those who favor fire. But if it had to \
+perish twice, I think I know enough of hate To say that for destruction \
+ice Is also great And would suffice.",
+                "C244c5dEa48e677cE7cAbD05BF8eC220b1a99Fc9"
+            )
+        ];
+        for (salt, answer) in correct_answers.iter() {
+            let addr: [u8; 20] = calc_addr(&deployer, salt.as_bytes());
+            let addr_str = hex::encode(addr);
+            assert_eq!(addr_str, answer.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn should_calculate_correctly_with_given_salt() {
+        let deployer =
+            hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8").unwrap();
+        let correct_answers: Vec<(&str, &str)> = vec![
+            (
+                "3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb",
+                "442188F25da4ac213D55aE81F1BFB421a4eb4562",
+            ),
+            (
+                "b5553de315e0edf504d9150af82dafa5c4667fa618ed0a6f19c69b41166c5510",
+                "551b9d8A7106Fdf98e68c4bf12Da1f23ad70C815",
+            ),
+            (
+                "0b42b6393c1f53060fe3ddbfcd7aadcca894465a5a438f69c87d790b2299b9b2",
+                "43d8e8C69fd771f7D3F4e25697Dadd3cC11D1cDB",
+            ),
+            (
+                "ead17456afde832907c72ba39033455130a8f4d540a869ba31312c2746bf9c4b",
+                "AB3D55404C5C21D18403A71aF5f6887BD0EC8d56",
+            ),
+        ];
+        for (salt, answer) in correct_answers.iter() {
+            let salt: [u8; 32] =
+                hex::decode(*salt).unwrap()[0..32].try_into().unwrap();
+            let addr: [u8; 20] = calc_addr_with_bytes(&deployer, &salt);
+            let addr_str = hex::encode(addr);
+            assert_eq!(addr_str, answer.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn should_calculate_create2_address() {
+        // examples from eip-1014.
+        let runs = [
+            (
+                "0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+                "4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38",
+            ),
+            (
+                "deadbeef00000000000000000000000000000000",
+                "000000000000000000000000feed000000000000000000000000000000000000",
+                "00",
+                "D04116cDd17beBE565EB2422F2497E06cC1C9833",
+            ),
+            (
+                "00000000000000000000000000000000deadbeef",
+                "00000000000000000000000000000000000000000000000000000000cafebabe",
+                "deadbeef",
+                "60f3f640a8508fC6a86d45DF051962668E1e8AC7",
+            ),
+        ];
+        for (deployer, salt, init_code, answer) in runs.iter() {
+            let deployer = hex::decode(deployer).unwrap();
+            let salt: [u8; 32] = hex::decode(salt).unwrap().try_into().unwrap();
+            let init_code_hash: [u8; 32] =
+                Keccak256::digest(hex::decode(init_code).unwrap()).into();
+            let addr = calc_create2_addr(&deployer, &salt, &init_code_hash);
+            assert_eq!(hex::encode(addr), answer.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn batch_should_match_single_calls() {
+        let deployer: Vec<u8> =
+            hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8").unwrap();
+        let mut rng = StdRng::seed_from_u64(539);
+        let salts: Vec<[u8; 32]> = (0..64).map(|_| rng.gen()).collect();
+        let addrs = calc_addr_batch(&deployer, &salts);
+        assert_eq!(addrs.len(), salts.len());
+        for (salt, addr) in salts.iter().zip(addrs.iter()) {
+            assert_eq!(*addr, calc_addr_with_bytes(&deployer, salt));
+        }
+        assert!(calc_addr_batch(&deployer, &[]).is_empty());
+    }
+
+    #[test]
+    fn typed_deployer_should_match_slice_api() {
+        let deployer: [u8; 20] =
+            hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let salt: [u8; 32] = hex::decode(
+            "3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb",
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        let addr = calc_addr_bytes20(&deployer, &salt);
+        assert_eq!(
+            hex::encode(addr),
+            "442188F25da4ac213D55aE81F1BFB421a4eb4562".to_lowercase()
+        );
+        assert_eq!(addr, calc_addr_with_bytes(&deployer, &salt));
+        assert_eq!(Create3::from(deployer).calc_addr_with_bytes(&salt), addr);
+    }
+
+    #[test]
+    fn create3_should_match_free_functions() {
+        let deployer: [u8; 20] =
+            hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let create3 = Create3::new(&deployer);
+        assert_eq!(create3.deployer(), deployer);
+        for salt in [
+            "3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb",
+            "b5553de315e0edf504d9150af82dafa5c4667fa618ed0a6f19c69b41166c5510",
+            "0b42b6393c1f53060fe3ddbfcd7aadcca894465a5a438f69c87d790b2299b9b2",
+        ] {
+            let salt: [u8; 32] = hex::decode(salt).unwrap().try_into().unwrap();
+            assert_eq!(
+                create3.calc_addr_with_bytes(&salt),
+                calc_addr_with_bytes(&deployer, &salt)
+            );
+        }
+        for salt in ["a", "b", "c", ""] {
+            assert_eq!(
+                create3.calc_addr(salt.as_bytes()),
+                calc_addr(&deployer, salt.as_bytes())
+            );
+        }
+    }
+
+    #[test]
+    fn should_calculate_correctly_with_given_int_salt() {
+        let deployer =
+            hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8").unwrap();
+        let runs = [
+            "26577169521080041468048461869858263792019104904445492498349147139819994214859",
+            "0x3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb",
+            "0X3AC225168DF54212A25C1C01FD35BEBFEA408FDAC2E31DDD6F80A4BBF9A5F1CB",
+        ];
+        for run in runs.iter() {
+            let addr = calc_addr_from_int_salt(&deployer, run).unwrap();
+            assert_eq!(
+                hex::encode(addr),
+                "442188F25da4ac213D55aE81F1BFB421a4eb4562".to_lowercase()
+            );
+        }
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let addr = calc_addr_with_bytes(&deployer, &one);
+        for run in ["1", "0x1", "0x01", "0001"].iter() {
+            assert_eq!(calc_addr_from_int_salt(&deployer, run).unwrap(), addr);
+        }
+        let max = calc_addr_with_bytes(&deployer, &[0xff; 32]);
+        assert_eq!(
+            calc_addr_from_int_salt(
+                &deployer,
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+            )
+            .unwrap(),
+            max
+        );
+    }
+
+    #[test]
+    fn calc_addr_from_int_salt_should_reject_invalid_integers() {
+        let deployer =
+            hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8").unwrap();
+        for run in ["", "0x", "12a", "-1", "1.5", "0xg"].iter() {
+            assert_eq!(
+                calc_addr_from_int_salt(&deployer, run),
+                Err(Create3GenerateSaltError::InvalidIntSalt)
+            );
+        }
+        let too_large = [
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936",
+            "0x10000000000000000000000000000000000000000000000000000000000000000",
+        ];
+        for run in too_large.iter() {
+            assert_eq!(
+                calc_addr_from_int_salt(&deployer, run),
+                Err(Create3GenerateSaltError::IntSaltTooLarge)
+            );
+        }
+    }
+
+    #[test]
+    fn should_calculate_proxy_address() {
+        let deployer: Vec<u8> =
+            hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8").unwrap();
+        let salt: [u8; 32] = hex::decode(
+            "3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb",
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        let mut preimage = vec![0xff];
+        preimage.extend_from_slice(&deployer);
+        preimage.extend_from_slice(&salt);
+        preimage.extend_from_slice(
+            &hex::decode(
+                "21c35dbe1b344a2488cf3321d6ce542f8e9f305544ff09e4993a62319a497c1f",
+            )
+            .unwrap(),
+        );
+        let proxy = calc_proxy_addr(&deployer, &salt);
+        assert_eq!(proxy, Keccak256::digest(&preimage)[12..]);
+        let mut preimage = vec![0xd6, 0x94];
+        preimage.extend_from_slice(&proxy);
+        preimage.push(0x01);
+        assert_eq!(
+            hex::encode(&Keccak256::digest(&preimage)[12..]),
+            "442188F25da4ac213D55aE81F1BFB421a4eb4562".to_lowercase()
+        );
+    }
+
+    #[test]
+    fn should_rlp_encode_create_nonces() {
+        let sender: [u8; 20] =
+            hex::decode("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        // well-known create addresses for this sender.
+        let runs = [
+            (0, "cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"),
+            (1, "343c43a37d37dff08ae8c4a11544c718abb4fcf8"),
+            (2, "f778b86fa74e846c4f0a1fbd1335fe81c00a0c91"),
+            (3, "fffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c"),
+        ];
+        for (nonce, answer) in runs.iter() {
+            let hash = Keccak256::digest(create_preimage(&sender, *nonce));
+            assert_eq!(hex::encode(&hash[12..]), *answer);
+        }
+        let sender_hex = hex::encode(sender);
+        let runs = [
+            (0x00, format!("d694{}80", sender_hex)),
+            (0x7f, format!("d694{}7f", sender_hex)),
+            (0x80, format!("d794{}8180", sender_hex)),
+            (0x0100, format!("d894{}820100", sender_hex)),
+            (u64::MAX, format!("de94{}88ffffffffffffffff", sender_hex)),
+        ];
+        for (nonce, answer) in runs.iter() {
+            assert_eq!(hex::encode(create_preimage(&sender, *nonce)), *answer);
+        }
+    }
+
+    #[test]
+    fn should_calculate_address_with_nonce() {
+        let deployer: Vec<u8> =
+            hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8").unwrap();
+        let salt: [u8; 32] = Keccak256::digest("nacl").into();
+        assert_eq!(
+            calc_addr_with_nonce(&deployer, &salt, 1),
+            calc_addr_with_bytes(&deployer, &salt)
+        );
+        let proxy = calc_proxy_addr(&deployer, &salt);
+        for nonce in [0, 0x80, 0x1234] {
+            let addr = calc_addr_with_nonce(&deployer, &salt, nonce);
+            assert_eq!(
+                addr,
+                Keccak256::digest(create_preimage(&proxy, nonce))[12..]
+            );
+            assert_ne!(addr, calc_addr_with_bytes(&deployer, &salt));
+        }
+    }
+
+    #[test]
+    fn preimages_should_reproduce_address() {
+        let deployer: Vec<u8> =
+            hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap();
+        let salt: [u8; 32] = Keccak256::digest("nacl").into();
+        let preimage = proxy_create2_preimage(&deployer, &salt);
+        assert_eq!(preimage.len(), 85);
+        assert_eq!(preimage[0], 0xff);
+        assert_eq!(&preimage[1..21], deployer.as_slice());
+        assert_eq!(&preimage[21..53], salt.as_slice());
+        let proxy: [u8; 20] =
+            Keccak256::digest(&preimage)[12..].try_into().unwrap();
+        let preimage = final_create_preimage(&proxy);
+        assert_eq!(
+            hex::encode(&preimage),
+            format!("d694{}01", hex::encode(proxy))
+        );
+        assert_eq!(
+            Keccak256::digest(&preimage)[12..],
+            calc_addr_with_bytes(&deployer, &salt)
+        );
+    }
+
+    #[test]
+    fn should_calculate_double_hashed_salt_address() {
+        let deployer: Vec<u8> =
+            hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap();
+        for salt in ["a", "nacl", ""] {
+            let once: [u8; 32] = Keccak256::digest(salt).into();
+            let twice: [u8; 32] = Keccak256::digest(once).into();
+            let addr = calc_addr_double_hashed_salt(&deployer, salt.as_bytes());
+            assert_eq!(addr, calc_addr_with_bytes(&deployer, &twice));
+            assert_ne!(addr, calc_addr(&deployer, salt.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn generate_fn_should_error_on_invalid_deployer_length() {
+        for len in [19, 21] {
+            let deployer = vec![0x11; len];
+            let err =
+                || Create3GenerateSaltError::InvalidDeployerLength { got: len };
+            assert_eq!(generate_salt(&deployer, "0"), Err(err()));
+            assert_eq!(
+                generate_salt_multithread(&deployer, "0", 2),
+                Err(err())
+            );
+            assert_eq!(
+                generate_salt_prefix(&deployer, "pfx_", "0"),
+                Err(err())
+            );
+            assert_eq!(generate_salt_counter(&deployer, "0", 0), Err(err()));
+            assert_eq!(
+                Create3Miner::new(&deployer).prefix("0").mine(),
+                Err(err())
+            );
+        }
+        // prefix errors are still reported first.
+        assert_eq!(
+            generate_salt(&[0x11; 19], "xyz"),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+
+    #[test]
+    fn should_calculate_deployer_mixed_address() {
+        let deployer: Vec<u8> =
+            hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap();
+        for salt in ["a", "b", "c"] {
+            let mut preimage = deployer.clone();
+            preimage.extend_from_slice(salt.as_bytes());
+            let mixed_salt: [u8; 32] = Keccak256::digest(&preimage).into();
+            let addr = calc_addr_deployer_mixed(&deployer, salt.as_bytes());
+            assert_eq!(addr, calc_addr_with_bytes(&deployer, &mixed_salt));
+            assert_ne!(addr, calc_addr(&deployer, salt.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn should_calculate_versioned_addresses() {
+        let deployer: Vec<u8> =
+            hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap();
+        let base_salt: [u8; 32] = Keccak256::digest("nacl").into();
+        let addrs = calc_addrs_versioned(&deployer, &base_salt, 3);
+        assert_eq!(addrs.len(), 4);
+        for (version, addr) in addrs.iter().enumerate() {
+            let mut preimage = base_salt.to_vec();
+            preimage.push(version as u8);
+            let salt: [u8; 32] = Keccak256::digest(&preimage).into();
+            assert_eq!(*addr, calc_addr_with_bytes(&deployer, &salt));
+            assert_eq!(
+                *addr,
+                calc_addr_versioned(&deployer, &base_salt, version as u8)
+            );
+        }
+        assert_ne!(addrs[0], addrs[1]);
+        assert_eq!(calc_addrs_versioned(&deployer, &base_salt, 255).len(), 256);
+    }
+
+    #[test]
+    fn should_generate_deployer_mixed_with_prefix() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for run in ["0", "ab", "Def"].iter() {
+            let (salt, mixed_salt) =
+                generate_salt_deployer_mixed(&deployer, run).unwrap();
+            let addr = calc_addr_deployer_mixed(&deployer, salt.as_bytes());
+            assert_eq!(addr, calc_addr_with_bytes(&deployer, &mixed_salt));
+            assert!(hex::encode(addr).starts_with(&run.to_lowercase()));
+        }
+    }
+
+    #[test]
+    fn should_generate_with_prefix() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let runs = ["0", "00", "000", "abc", "123", "789", "Def"];
+        for run in runs.iter() {
+            let salt = generate_salt(&deployer, run).unwrap();
+            let addr: [u8; 20] = calc_addr_with_bytes(&deployer, &salt.1);
+            let addr_string = calc_addr(&deployer, salt.0.as_bytes());
+            assert_eq!(addr, addr_string);
+            assert!(hex::encode(addr).starts_with(&run.to_lowercase()));
+        }
+    }
+
+    #[test]
+    fn should_generate_same_salt_from_same_seed() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for run in ["0", "ab", "Def"].iter() {
+            let first = generate_salt_with_rng(
+                &deployer,
+                run,
+                &mut StdRng::seed_from_u64(1608),
+            )
+            .unwrap();
+            let second = generate_salt_with_rng(
+                &deployer,
+                run,
+                &mut StdRng::seed_from_u64(1608),
+            )
+            .unwrap();
+            assert_eq!(first, second);
+            assert!(hex::encode(calc_addr_with_bytes(&deployer, &first.1))
+                .starts_with(&run.to_lowercase()));
+        }
+    }
+
+    #[test]
+    fn search_rng_salts_should_match_prefix() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let mut rng = search_rng();
+        let mut salts = HashSet::new();
+        for run in ["0", "ab", "Def"].iter() {
+            let (salt, salt_bytes) =
+                generate_salt_with_rng(&deployer, run, &mut rng).unwrap();
+            assert_eq!(
+                calc_addr(&deployer, salt.as_bytes()),
+                calc_addr_with_bytes(&deployer, &salt_bytes)
+            );
+            assert!(hex::encode(calc_addr_with_bytes(&deployer, &salt_bytes))
+                .starts_with(&run.to_lowercase()));
+            salts.insert(salt);
+        }
+        assert_eq!(salts.len(), 3);
+        assert_ne!(
+            random_salt(&mut search_rng(), 10),
+            random_salt(&mut search_rng(), 10)
+        );
+    }
+
+    #[test]
+    fn salt_from_counter_should_pad_big_endian_counter() {
+        assert_eq!(
+            hex::encode(salt_from_counter(1)),
+            format!("{}01", "00".repeat(31))
+        );
+        assert_eq!(salt_from_counter(0), [0u8; 32]);
+        assert_eq!(
+            hex::encode(salt_from_counter(0x0102_0304_0506_0708)),
+            format!("{}0102030405060708", "00".repeat(24))
+        );
+        assert_eq!(salt_from_counter(u64::MAX)[24..], [0xff; 8]);
+        let salts: HashSet<[u8; 32]> =
+            (0..1000).chain([u64::MAX]).map(salt_from_counter).collect();
+        assert_eq!(salts.len(), 1001);
+    }
+
+    #[test]
+    fn should_generate_same_salt_from_same_counter() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let first = generate_salt_counter(&deployer, "ab", 0).unwrap();
+        assert_eq!(generate_salt_counter(&deployer, "ab", 0).unwrap(), first);
+        assert_eq!(
+            generate_salt_counter(&deployer, "ab", first.0).unwrap(),
+            first
+        );
+        assert_eq!(first.1, salt_from_counter(first.0));
+        assert!(hex::encode(calc_addr_with_bytes(&deployer, &first.1))
+            .starts_with("ab"));
+
+        // resuming past a hit skips it.
+        let second =
+            generate_salt_counter(&deployer, "ab", first.0 + 1).unwrap();
+        assert!(second.0 > first.0);
+        assert!(hex::encode(calc_addr_with_bytes(&deployer, &second.1))
+            .starts_with("ab"));
+    }
+
+    #[test]
+    fn should_generate_multithread_with_prefix() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let runs = ["0", "00", "000", "abcd", "123", "789", "Def"];
+        for run in runs.iter() {
+            let salt = generate_salt_multithread(&deployer, run, 6).unwrap();
+            let addr: [u8; 20] = calc_addr_with_bytes(&deployer, &salt.1);
+            assert_eq!(calc_addr(&deployer, salt.0.as_bytes()), addr);
+            assert!(hex::encode(addr).starts_with(&run.to_lowercase()));
+        }
+    }
+
+    #[test]
+    fn multithread_should_use_all_cores_for_zero_threads() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let (salt, digested_salt) =
+            generate_salt_multithread(&deployer, "0", 0).unwrap();
+        assert_eq!(Keccak256::digest(&salt).as_slice(), digested_salt);
+        assert!(hex::encode(calc_addr_with_bytes(&deployer, &digested_salt))
+            .starts_with('0'));
+        let cores = std::thread::available_parallelism().unwrap().get();
+        assert_eq!(resolve_thread_count(0), cores);
+        assert_eq!(resolve_thread_count(1), 1);
+    }
+
+    #[test]
+    fn multithread_should_cap_thread_count_at_cores() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let cores = std::thread::available_parallelism().unwrap().get();
+        assert_eq!(resolve_thread_count(300), cores.min(300));
+        let (_, digested_salt) =
+            generate_salt_multithread(&deployer, "1", 300).unwrap();
+        assert!(hex::encode(calc_addr_with_bytes(&deployer, &digested_salt))
+            .starts_with('1'));
+    }
+
+    #[test]
+    fn multithread_should_always_return_salt_under_contention() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        // easy prefixes make workers finish at nearly the same time.
+        for run in 0..100 {
+            let prefix = ["", "0", "f"][run % 3];
+            let (salt, digested_salt) =
+                generate_salt_multithread(&deployer, prefix, 64).unwrap();
+            assert!(!salt.is_empty());
+            assert_eq!(Keccak256::digest(&salt).as_slice(), digested_salt);
+            assert!(hex::encode(calc_addr_with_bytes(
+                &deployer,
+                &digested_salt
+            ))
+            .starts_with(prefix));
+        }
+    }
+
+    #[test]
+    fn multithread_salts_should_always_reverify() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for prefix in ["", "0", "Ab", "0x7"] {
+            let nibbles = to_nibbles(&sanitize_hex_prefix(prefix).unwrap());
+            for _ in 0..3 {
+                let (salt, salt_hash) =
+                    generate_salt_prefix_multithread(&deployer, "", prefix, 2)
+                        .unwrap();
+                assert!(verify_prefix_match(
+                    &deployer, &salt, &salt_hash, &nibbles
+                ));
             }
-        });
-        threads.push(handle);
+        }
+        // a stale hash or another prefix fails the check.
+        let (salt, salt_hash) =
+            generate_salt_multithread(&deployer, "ab", 2).unwrap();
+        let ab = to_nibbles("ab");
+        assert!(verify_prefix_match(&deployer, &salt, &salt_hash, &ab));
+        assert!(!verify_prefix_match(&deployer, "other", &salt_hash, &ab));
+        assert!(!verify_prefix_match(&deployer, &salt, &[0; 32], &ab));
+        assert!(!verify_prefix_match(
+            &deployer,
+            &salt,
+            &salt_hash,
+            &to_nibbles("ac")
+        ));
+    }
+
+    #[test]
+    fn multithread_should_return_not_found_without_a_match() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let attempts = AtomicU64::new(0);
+        assert_eq!(
+            search_salt_parallel(
+                &deployer,
+                "",
+                "abcdef0123",
+                2,
+                &AtomicBool::new(false),
+                1_000,
+                &attempts
+            ),
+            Err(Create3GenerateSaltError::NotFound { attempts: 1_000 })
+        );
+        assert_eq!(attempts.load(Ordering::Relaxed), 1_000);
+        assert_eq!(
+            search_salt_parallel(
+                &deployer,
+                "",
+                "0",
+                2,
+                &AtomicBool::new(false),
+                0,
+                &attempts
+            ),
+            Err(Create3GenerateSaltError::NotFound { attempts: 0 })
+        );
+        assert_eq!(attempts.load(Ordering::Relaxed), 1_000);
+    }
+
+    #[test]
+    fn multithread_should_error_when_a_worker_panics() {
+        for thread_count in [1, 3] {
+            assert_eq!(
+                generate_salt_multithread(
+                    &PANICKING_DEPLOYER,
+                    "abcdef",
+                    thread_count
+                ),
+                Err(Create3GenerateSaltError::WorkerPanicked)
+            );
+        }
+    }
+
+    #[test]
+    fn should_generate_salt_for_pattern() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for pattern in ["d?e", "??a", "?", "", "0x?B?"] {
+            let (salt, salt_hash) =
+                generate_salt_pattern(&deployer, pattern, 2).unwrap();
+            assert_eq!(Keccak256::digest(&salt).as_slice(), salt_hash);
+            let address = hex::encode(calc_addr(&deployer, salt.as_bytes()));
+            let pattern = pattern.trim_start_matches("0x").to_lowercase();
+            for (a, p) in address.chars().zip(pattern.chars()) {
+                assert!(p == '?' || a == p, "{} vs {}", address, pattern);
+            }
+        }
+    }
+
+    #[test]
+    fn should_generate_salt_containing_any_needle() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        // the first needle is far too long to turn up in a test.
+        let needles = ["0123456789abcdef0123", "0xBE"];
+        let (salt, salt_hash, needle) =
+            generate_salt_contains_any(&deployer, &needles, 2).unwrap();
+        assert_eq!(needle, 1);
+        assert_eq!(Keccak256::digest(&salt).as_slice(), salt_hash);
+        assert!(
+            hex::encode(calc_addr(&deployer, salt.as_bytes())).contains("be")
+        );
+
+        assert_eq!(
+            generate_salt_contains_any(&deployer, &[], 2),
+            Err(Create3GenerateSaltError::NoNeedles)
+        );
+        assert_eq!(
+            generate_salt_contains_any(&deployer, &["ab", "xyz"], 2),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+
+    #[test]
+    fn should_leetify_words() {
+        assert_eq!(leetify("beef"), Some("beef".to_owned()));
+        assert_eq!(leetify("Boost"), Some("b0057".to_owned()));
+        assert_eq!(leetify("c0ffee"), Some("c0ffee".to_owned()));
+        assert_eq!(leetify("hello"), None);
+        assert_eq!(leetify("ba be"), None);
+    }
+
+    #[test]
+    fn should_generate_salt_for_word() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let (salt, salt_hash) = generate_salt_word(&deployer, "so", 2).unwrap();
+        assert_eq!(Keccak256::digest(&salt).as_slice(), salt_hash);
+        assert!(
+            hex::encode(calc_addr(&deployer, salt.as_bytes())).contains("50")
+        );
+        assert_eq!(
+            generate_salt_word(&deployer, "hello", 2),
+            Err(Create3GenerateSaltError::UnmappableWord)
+        );
+    }
+
+    #[test]
+    fn should_calculate_checksummed_address() {
+        let deployer =
+            hex::decode("De2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343B5").unwrap();
+        assert_eq!(
+            calc_addr_checksummed(&deployer, b"nacl"),
+            "0x8b9A192B07bb8de5615545C620738c2713B97D4d"
+        );
+        for salt in ["", "a", "v2_deadbeef"] {
+            assert_eq!(
+                calc_addr_checksummed(&deployer, salt.as_bytes()),
+                to_checksum_address(&calc_addr(&deployer, salt.as_bytes()))
+            );
+        }
+    }
+
+    #[test]
+    fn calc_addr_str_should_hash_the_whole_salt() {
+        let deployer =
+            hex::decode("De2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343B5").unwrap();
+        assert_eq!(
+            calc_addr_str(&deployer, "nacl"),
+            hex::decode("8b9A192B07bb8de5615545C620738c2713B97D4d").unwrap()[..]
+        );
+        let long = "two roads diverged in a yellow wood. ".repeat(128);
+        assert!(long.len() > 4096);
+        let address = calc_addr_str(&deployer, &long);
+        assert_eq!(
+            address,
+            calc_addr_with_bytes(&deployer, &Keccak256::digest(&long).into())
+        );
+        // a change in the very last byte still moves the address.
+        let mut changed = long.clone();
+        changed.pop();
+        changed.push('!');
+        assert_ne!(calc_addr_str(&deployer, &changed), address);
+        assert_ne!(calc_addr_str(&deployer, &long[..32]), address);
+    }
+
+    #[test]
+    fn hex_salt_should_hash_decoded_bytes_not_text() {
+        let deployer =
+            hex::decode("De2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343B5").unwrap();
+        let hex_salt = calc_addr_hex_salt(&deployer, "0x1234").unwrap();
+        assert_eq!(hex_salt, calc_addr(&deployer, &[0x12, 0x34]));
+        assert_eq!(calc_addr_hex_salt(&deployer, "1234"), Ok(hex_salt));
+        assert_eq!(calc_addr_hex_salt(&deployer, "0X1234"), Ok(hex_salt));
+        assert_ne!(hex_salt, calc_addr(&deployer, b"0x1234"));
+        assert_ne!(hex_salt, calc_addr(&deployer, b"1234"));
+        // "nacl" is the utf8 salt of a known vector.
+        assert_eq!(
+            calc_addr_hex_salt(&deployer, &hex::encode("nacl")),
+            Ok(calc_addr(&deployer, b"nacl"))
+        );
+        for bad in ["0x123", "nacl", "0x0x12"] {
+            assert_eq!(
+                calc_addr_hex_salt(&deployer, bad),
+                Err(Create3GenerateSaltError::SaltNotHexEncoded)
+            );
+        }
+    }
+
+    #[test]
+    fn salt_result_should_display_on_one_line() {
+        let deployer =
+            hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8").unwrap();
+        let salt_hash = parse_salt_hash(
+            "3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb",
+        )
+        .unwrap();
+        let result = SaltResult {
+            salt: "say \"hi\"".to_owned(),
+            salt_hash,
+            address: calc_addr_with_bytes(&deployer, &salt_hash),
+            attempts: 1,
+        };
+        let line = result.to_string();
+        assert_eq!(
+            line,
+            "address=0x442188F25da4ac213D55aE81F1BFB421a4eb4562 \
+             salt=\"say \\\"hi\\\"\" \
+             salt_hash=0x3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb"
+        );
+        assert!(line.contains("0x442188F25da4ac213D55aE81F1BFB421a4eb4562"));
+        assert!(line.contains(&format!("0x{}", hex::encode(salt_hash))));
+    }
+
+    #[test]
+    fn multithread_should_count_attempts() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let attempts = Arc::new(AtomicU64::new(0));
+        let (salt, salt_hash, count) =
+            generate_salt_prefix_multithread_counted(
+                &deployer,
+                "v1_",
+                "a",
+                2,
+                attempts.clone(),
+            )
+            .unwrap();
+        assert!(salt.starts_with("v1_"));
+        assert_eq!(Keccak256::digest(&salt).as_slice(), salt_hash);
+        assert!(count > 0);
+        assert_eq!(attempts.load(Ordering::Relaxed), count);
+
+        // a shared counter keeps adding up across searches.
+        let (_, _, total) = generate_salt_prefix_multithread_counted(
+            &deployer,
+            "",
+            "b",
+            2,
+            attempts.clone(),
+        )
+        .unwrap();
+        assert!(total > count);
+    }
+
+    #[test]
+    fn should_format_salt_hash_as_bytes32_literal() {
+        let salt_hash = parse_salt_hash(
+            "3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb",
+        )
+        .unwrap();
+        for salt in [salt_hash, [0; 32], [0xff; 32]] {
+            let literal = salt_hash_to_bytes32_literal(&salt);
+            assert_eq!(literal.len(), 66);
+            assert_eq!(literal, format!("0x{}", hex::encode(salt)));
+            assert_eq!(parse_salt_hash(&literal), Ok(salt));
+        }
+    }
+
+    #[test]
+    fn should_parse_salt_hash() {
+        let hex =
+            "3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb";
+        let expected: [u8; 32] = hex::decode(hex).unwrap().try_into().unwrap();
+        assert_eq!(parse_salt_hash(hex), Ok(expected));
+        assert_eq!(parse_salt_hash(&format!("0x{}", hex)), Ok(expected));
+        assert_eq!(
+            parse_salt_hash(&format!("0x{}", hex.to_uppercase())),
+            Ok(expected)
+        );
+        assert_eq!(parse_salt_hash(&format!("0X{}", hex)), Ok(expected));
+        for bad in [
+            "",
+            "0x",
+            &hex[..62],
+            &format!("{}00", hex),
+            &format!("0x{}zz", &hex[..62]),
+            &format!("{}é", &hex[..62]),
+        ] {
+            assert_eq!(
+                parse_salt_hash(bad),
+                Err(Create3GenerateSaltError::InvalidSaltHash)
+            );
+        }
+    }
+
+    #[test]
+    fn salt_hash_should_cover_a_long_salt_prefix() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let salt_prefix = "namespace/".repeat(25) + "v1_";
+        let results = [
+            generate_salt_prefix(&deployer, &salt_prefix, "a").unwrap(),
+            generate_salt_prefix_multithread(&deployer, &salt_prefix, "a", 2)
+                .unwrap(),
+        ];
+        for (salt, salt_hash) in results {
+            let random = salt.strip_prefix(&salt_prefix).unwrap();
+            assert_eq!(random.len(), 7);
+            let digest: [u8; 32] =
+                Keccak256::digest(format!("{}{}", salt_prefix, random)).into();
+            assert_eq!(digest, salt_hash);
+            assert!(hex::encode(calc_addr_with_bytes(&deployer, &salt_hash))
+                .starts_with('a'));
+        }
+
+        let too_long = "x".repeat(MAX_SALT_PREFIX_LEN + 1);
+        assert_eq!(
+            generate_salt_prefix(&deployer, &too_long, "a"),
+            Err(Create3GenerateSaltError::SaltPrefixTooLong)
+        );
+        assert_eq!(
+            generate_salt_prefix_multithread(&deployer, &too_long, "a", 2),
+            Err(Create3GenerateSaltError::SaltPrefixTooLong)
+        );
+        assert_eq!(
+            Create3Miner::new(&deployer).salt_prefix(&too_long).mine(),
+            Err(Create3GenerateSaltError::SaltPrefixTooLong)
+        );
+    }
+
+    #[test]
+    fn should_generate_salt_suffix_multithread() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for suffix in ["", "7", "0xBe", "00"] {
+            let (salt, salt_hash) =
+                generate_salt_suffix_multithread(&deployer, suffix, 2).unwrap();
+            assert_eq!(Keccak256::digest(&salt).as_slice(), salt_hash);
+            let expected = sanitize_hex_prefix(suffix).unwrap();
+            assert!(hex::encode(calc_addr(&deployer, salt.as_bytes()))
+                .ends_with(&expected));
+        }
+        assert_eq!(
+            generate_salt_suffix_multithread(&deployer, "xyz", 2),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+
+    #[test]
+    fn should_generate_salt_for_uppercase_hex_prefix() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for prefix in ["DE", "0xA7", "1", ""] {
+            let (salt, salt_hash) =
+                generate_salt_uppercase_hex(&deployer, prefix, 2).unwrap();
+            assert_eq!(Keccak256::digest(&salt).as_slice(), salt_hash);
+            let upper =
+                hex::encode_upper(calc_addr(&deployer, salt.as_bytes()));
+            assert!(upper.starts_with(prefix.trim_start_matches("0x")));
+        }
+        assert_eq!(
+            generate_salt_uppercase_hex(&deployer, "dEAD", 2),
+            Err(Create3GenerateSaltError::PrefixNotUppercase)
+        );
+        assert_eq!(
+            generate_salt_uppercase_hex(&deployer, "DEAG", 2),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
     }
-    for t in threads {
-        t.join().unwrap();
+
+    #[test]
+    fn should_generate_salt_for_predicate() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let (salt, salt_hash) = generate_salt_predicate(&deployer, 2, |a| {
+            a[0] == 0x00 && a[19] == 0xff
+        })
+        .unwrap();
+        assert_eq!(Keccak256::digest(&salt).as_slice(), salt_hash);
+        let address = calc_addr(&deployer, salt.as_bytes());
+        assert_eq!((address[0], address[19]), (0x00, 0xff));
+        assert_eq!(
+            generate_salt_predicate(&deployer[..19], 2, |_| true),
+            Err(Create3GenerateSaltError::InvalidDeployerLength { got: 19 })
+        );
     }
-    let read_lock = lock.read().unwrap();
-    Ok((read_lock.0.clone(), read_lock.1.clone()))
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{
-        calc_addr, calc_addr_with_bytes, generate_salt,
-        generate_salt_multithread, generate_salt_prefix,
-        generate_salt_prefix_multithread, Create3GenerateSaltError,
-    };
-    use sha3::{Digest, Keccak256};
+    #[test]
+    fn should_generate_salt_masked_on_first_and_last_byte() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let mut mask = [0u8; 20];
+        let mut target = [0u8; 20];
+        // high nibble of the first byte and low nibble of the last.
+        mask[0] = 0xf0;
+        target[0] = 0xa0;
+        mask[19] = 0x0f;
+        target[19] = 0x0b;
+        let (salt, salt_hash) =
+            generate_salt_masked(&deployer, &mask, &target, 2).unwrap();
+        assert_eq!(Keccak256::digest(&salt).as_slice(), salt_hash);
+        let address = calc_addr(&deployer, salt.as_bytes());
+        assert_eq!(address[0] >> 4, 0xa);
+        assert_eq!(address[19] & 0x0f, 0xb);
+        assert!(matches_masked(&address, &mask, &target));
+        assert!(matches_masked(&address, &[0; 20], &[0xff; 20]));
+        let mut wrong = target;
+        wrong[19] = 0x0c;
+        assert!(!matches_masked(&address, &mask, &wrong));
+    }
 
     #[test]
-    fn should_calculate_correctly_with_given_salt_string() {
+    fn should_parse_and_match_patterns() {
+        assert_eq!(
+            parse_pattern("de?d??ef").unwrap(),
+            vec![(0, 0xd), (1, 0xe), (3, 0xd), (6, 0xe), (7, 0xf)]
+        );
+        let mut addr = [0u8; 20];
+        addr[..4].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        assert!(matches_pattern(&addr, &parse_pattern("de?d??ef").unwrap()));
+        assert!(matches_pattern(&addr, &parse_pattern("????").unwrap()));
+        assert!(!matches_pattern(&addr, &parse_pattern("de?d??ee").unwrap()));
+        for pattern in ["de*d", "g", "de d"] {
+            assert_eq!(
+                parse_pattern(pattern),
+                Err(Create3GenerateSaltError::InvalidPatternCharacter)
+            );
+        }
+        assert_eq!(
+            generate_salt_pattern(&[0; 20], &"?".repeat(41), 1),
+            Err(Create3GenerateSaltError::PrefixTooLong)
+        );
+    }
+
+    #[test]
+    fn generate_salt_cancellable_should_return_cancelled_when_stopped() {
         let deployer: Vec<u8> =
-            hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap();
-        let correct_answers: Vec<(&str, &str)> = vec![
-            ("a", "BFf47440D3A5E59714F1D995F8b105E2a04AB46A"),
-            ("b", "7E10Ca8fa1c8e1528601Fea82F51646182f835b8"),
-            ("c", "70b556548FF0161082fB751d5E372eFa0133805C"),
-            (
-                "some say the world will end in fire, some say in ice. from what \
-i’ve tasted of desire i hold with those who favor fire. but if it had to \
-perish twice, i think i know enough of hate to say that for destruction \
-ice is also great and would suffice.",
-                "C244c5dEa48e677cE7cAbD05BF8eC220b1a99Fc9"
-            )
-        ];
-        for (salt, answer) in correct_answers.iter() {
-            let addr: [u8; 20] = calc_addr(&deployer, salt.as_bytes());
-            let addr_str = hex::encode(addr);
-            assert_eq!(addr_str, answer.to_lowercase());
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let start = Instant::now();
+        let stop = Arc::new(AtomicBool::new(true));
+        assert_eq!(
+            generate_salt_cancellable(&deployer, "abcdef0123", 4, stop),
+            Err(Create3GenerateSaltError::Cancelled)
+        );
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        // raising the flag from another thread stops a running search.
+        let stop = Arc::new(AtomicBool::new(false));
+        let canceller = {
+            let stop = stop.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                stop.store(true, Ordering::Relaxed);
+            })
+        };
+        assert_eq!(
+            generate_salt_cancellable(&deployer, "abcdef0123", 4, stop),
+            Err(Create3GenerateSaltError::Cancelled)
+        );
+        canceller.join().unwrap();
+    }
+
+    #[test]
+    fn should_generate_cancellable_with_prefix() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let stop = Arc::new(AtomicBool::new(false));
+        let (salt, digested_salt) =
+            generate_salt_cancellable(&deployer, "ab", 4, stop).unwrap();
+        let addr = calc_addr_with_bytes(&deployer, &digested_salt);
+        assert_eq!(addr, calc_addr(&deployer, salt.as_bytes()));
+        assert!(hex::encode(addr).starts_with("ab"));
+    }
+
+    #[test]
+    fn should_generate_bounded_within_budget() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let (salt, digested_salt) =
+            generate_salt_bounded(&deployer, "a", 10_000).unwrap();
+        let addr = calc_addr_with_bytes(&deployer, &digested_salt);
+        assert_eq!(addr, calc_addr(&deployer, salt.as_bytes()));
+        assert!(hex::encode(addr).starts_with('a'));
+    }
+
+    #[test]
+    fn generate_salt_bounded_should_error_when_budget_is_exhausted() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        assert_eq!(
+            generate_salt_bounded(&deployer, "abcdef012345", 1_000),
+            Err(Create3GenerateSaltError::NotFound { attempts: 1_000 })
+        );
+    }
+
+    #[test]
+    fn search_should_report_progress_until_stopped() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let stop = AtomicBool::new(false);
+        let attempts = AtomicU64::new(0);
+        let mut reports = Vec::new();
+        let result = search_parallel(
+            &deployer,
+            "",
+            2,
+            SearchControl {
+                stop: &stop,
+                max_attempts: u64::MAX,
+                attempts: &attempts,
+                progress: Some((Duration::from_millis(1), &mut |stats| {
+                    reports.push(stats);
+                    if reports.len() == 3 {
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                })),
+            },
+            |_, _| false,
+        );
+        assert_eq!(result, Err(Create3GenerateSaltError::Cancelled));
+        // nothing is reported once the search was cancelled.
+        assert_eq!(reports.len(), 3);
+        for pair in reports.windows(2) {
+            assert!(pair[0].attempts <= pair[1].attempts);
+            assert!(pair[0].elapsed < pair[1].elapsed);
         }
+        assert!(attempts.load(Ordering::Relaxed) >= reports[2].attempts);
     }
 
     #[test]
-    fn should_calculate_correctly_with_given_salt() {
-        let deployer =
-            hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8").unwrap();
-        let correct_answers: Vec<(&str, &str)> = vec![
-            (
-                "3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb",
-                "442188F25da4ac213D55aE81F1BFB421a4eb4562",
-            ),
-            (
-                "b5553de315e0edf504d9150af82dafa5c4667fa618ed0a6f19c69b41166c5510",
-                "551b9d8A7106Fdf98e68c4bf12Da1f23ad70C815",
-            ),
-            (
-                "0b42b6393c1f53060fe3ddbfcd7aadcca894465a5a438f69c87d790b2299b9b2",
-                "43d8e8C69fd771f7D3F4e25697Dadd3cC11D1cDB",
+    fn search_should_count_every_attempt_of_a_partial_chunk() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let attempts = AtomicU64::new(0);
+        let result = search_parallel(
+            &deployer,
+            "",
+            3,
+            SearchControl {
+                stop: &AtomicBool::new(false),
+                max_attempts: 5_000,
+                attempts: &attempts,
+                progress: None,
+            },
+            |_, _| false,
+        );
+        assert_eq!(
+            result,
+            Err(Create3GenerateSaltError::NotFound { attempts: 5_000 })
+        );
+        assert_eq!(attempts.load(Ordering::Relaxed), 5_000);
+    }
+
+    #[test]
+    fn should_generate_from_alphabet() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let alphabet = b"xyz-_";
+        let (salt, digested_salt) =
+            generate_salt_alphabet(&deployer, "ab", alphabet, 12, true)
+                .unwrap();
+        assert_eq!(salt.len(), 12);
+        assert!(salt.iter().all(|b| alphabet.contains(b)));
+        let addr = calc_addr_with_bytes(&deployer, &digested_salt);
+        assert_eq!(addr, calc_addr(&deployer, &salt));
+        assert!(hex::encode(addr).starts_with("ab"));
+
+        // non-printable bytes are fine unless printable_only is set.
+        let alphabet = b"ab\x07\x1b";
+        assert!(
+            generate_salt_alphabet(&deployer, "a", alphabet, 8, false).is_ok()
+        );
+    }
+
+    #[test]
+    fn generate_salt_alphabet_should_error_on_non_printable_alphabet() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        assert_eq!(
+            generate_salt_alphabet(&deployer, "a", b"ab\x07c\x1b\x7f", 8, true),
+            Err(Create3GenerateSaltError::NonPrintableAlphabet {
+                bytes: vec![0x07, 0x1b, 0x7f]
+            })
+        );
+        assert_eq!(
+            generate_salt_alphabet(&deployer, "a", b"", 8, true),
+            Err(Create3GenerateSaltError::EmptyAlphabet)
+        );
+    }
+
+    #[test]
+    fn should_revalidate_salts_under_new_deployer() {
+        let old_deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let new_deployer: Vec<u8> =
+            hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap();
+        let mut salts: Vec<[u8; 32]> = (0..4)
+            .map(|_| generate_salt(&old_deployer, "ab").unwrap().1)
+            .collect();
+        // a salt mined for the new deployer that doesn't match the old one.
+        let new_only = (0..)
+            .map(|_| generate_salt(&new_deployer, "ab").unwrap().1)
+            .find(|salt| {
+                !hex::encode(calc_addr_with_bytes(&old_deployer, salt))
+                    .starts_with("ab")
+            })
+            .unwrap();
+        salts.push(new_only);
+        let mut expected: Vec<bool> = salts[..4]
+            .iter()
+            .map(|salt| {
+                hex::encode(calc_addr_with_bytes(&new_deployer, salt))
+                    .starts_with("ab")
+            })
+            .collect();
+        expected.push(false);
+        assert_eq!(
+            revalidate_batch(&old_deployer, &new_deployer, &salts, "AB")
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            revalidate_batch(&old_deployer, &old_deployer, &salts, "ab")
+                .unwrap(),
+            vec![true, true, true, true, false]
+        );
+        assert_eq!(
+            revalidate_batch(&old_deployer, &new_deployer, &salts, "xyz"),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+        assert_eq!(
+            revalidate_batch(&[0; 19], &new_deployer, &salts, "ab"),
+            Err(Create3GenerateSaltError::InvalidDeployerLength { got: 19 })
+        );
+    }
+
+    #[test]
+    fn should_find_salt_for_target_address() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        // the seeded rng draws this salt on its third attempt.
+        let mut rng = StdRng::seed_from_u64(1608);
+        let _: [[u8; 32]; 2] = rng.gen();
+        let salt: [u8; 32] = rng.gen();
+        let target = calc_addr_with_bytes(&deployer, &salt);
+        let targets = [[0x11; 20], target];
+        assert_eq!(
+            search_any_address(
+                &deployer,
+                &targets,
+                3,
+                &mut StdRng::seed_from_u64(1608)
             ),
-            (
-                "ead17456afde832907c72ba39033455130a8f4d540a869ba31312c2746bf9c4b",
-                "AB3D55404C5C21D18403A71aF5f6887BD0EC8d56",
+            Some((salt, target))
+        );
+        assert_eq!(
+            search_any_address(
+                &deployer,
+                &targets,
+                2,
+                &mut StdRng::seed_from_u64(1608)
             ),
+            None
+        );
+    }
+
+    #[test]
+    fn generate_salt_for_any_address_should_give_up_after_budget() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        assert_eq!(
+            generate_salt_for_any_address(&deployer, &[[0x11; 20]], 1_000),
+            None
+        );
+        assert_eq!(generate_salt_for_any_address(&deployer, &[], 1_000), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn salt_result_should_round_trip_through_json() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let salt = "nacl".to_owned();
+        let result = SaltResult {
+            salt_hash: Keccak256::digest(&salt).into(),
+            address: calc_addr(&deployer, salt.as_bytes()),
+            salt,
+            attempts: 42,
+        };
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains(&format!(
+            "\"address\":\"0x{}\"",
+            hex::encode(result.address)
+        )));
+        assert!(json.contains("\"salt\":\"nacl\""));
+        assert_eq!(serde_json::from_str::<SaltResult>(&json).unwrap(), result);
+        assert!(serde_json::from_str::<SaltResult>(
+            &json.replace(&hex::encode(result.address), "00")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn should_rank_results_by_score() {
+        let result = |address: [u8; 20], salt: &str| SaltResult {
+            salt: salt.to_owned(),
+            salt_hash: Keccak256::digest(salt).into(),
+            address,
+            attempts: 1,
+        };
+        let mut address = [0xff; 20];
+        address[0] = 0x00;
+        let results = [
+            result([0xff; 20], "a"),
+            result(address, "b"),
+            result([0x00; 20], "c"),
+            result([0xff; 20], "d"),
         ];
-        for (salt, answer) in correct_answers.iter() {
-            let salt: [u8; 32] =
-                hex::decode(*salt).unwrap()[0..32].try_into().unwrap();
-            let addr: [u8; 20] = calc_addr_with_bytes(&deployer, &salt);
-            let addr_str = hex::encode(addr);
-            assert_eq!(addr_str, answer.to_lowercase());
-        }
+        let zero_bytes =
+            |addr: &[u8; 20]| addr.iter().filter(|&&b| b == 0).count() as i64;
+        let ranked: Vec<&str> = rank_results(&results, zero_bytes)
+            .iter()
+            .map(|r| r.salt.as_str())
+            .collect();
+        assert_eq!(ranked, ["c", "b", "a", "d"]);
+        assert!(rank_results(&[], zero_bytes).is_empty());
     }
 
     #[test]
-    fn should_generate_with_prefix() {
+    fn should_verify_salts() {
+        let deployer: Vec<u8> =
+            hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8").unwrap();
+        let (salt, _) = generate_salt(&deployer, "44").unwrap();
+        assert!(verify_salt(&deployer, salt.as_bytes(), "44"));
+        assert!(verify_salt(&deployer, salt.as_bytes(), "0x4"));
+        assert!(!verify_salt(&deployer, salt.as_bytes(), "45"));
+        assert!(!verify_salt(&deployer, salt.as_bytes(), "xyz"));
+
+        let expected = calc_addr(&deployer, salt.as_bytes());
+        assert!(verify_salt_address(&deployer, salt.as_bytes(), &expected));
+        assert!(!verify_salt_address(&deployer, b"other", &expected));
+    }
+
+    #[test]
+    fn should_generate_custom_salt() {
         let deployer: Vec<u8> =
             hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
-        let runs = vec!["0", "00", "000", "abc", "123", "789", "Def"];
-        for run in runs.iter() {
-            let salt = generate_salt(&deployer, run).unwrap();
-            let addr: [u8; 20] = calc_addr_with_bytes(&deployer, &salt.1);
-            let addr_string = calc_addr(&deployer, salt.0.as_bytes());
-            assert_eq!(addr, addr_string);
-            assert!(hex::encode(addr).starts_with(&run.to_lowercase()));
+        let runs = [
+            (Charset::Alphanumeric, 4),
+            (Charset::HexLower, 6),
+            (Charset::Numeric, 3),
+        ];
+        for (charset, salt_len) in runs.iter() {
+            let (salt, salt_bytes) =
+                generate_salt_custom(&deployer, "a", *salt_len, *charset)
+                    .unwrap();
+            assert_eq!(salt.len(), *salt_len);
+            assert!(salt.bytes().all(|b| charset.alphabet().contains(&b)));
+            assert_eq!(Keccak256::digest(&salt).as_slice(), salt_bytes);
+            assert!(hex::encode(calc_addr_with_bytes(&deployer, &salt_bytes))
+                .starts_with('a'));
         }
+        assert!(Charset::HexLower
+            .alphabet()
+            .iter()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(b)));
     }
 
     #[test]
-    fn should_generate_multithread_with_prefix() {
+    fn generate_salt_custom_should_error_if_salt_space_is_too_small() {
         let deployer: Vec<u8> =
             hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
-        let runs = vec!["0", "00", "000", "abcd", "123", "789", "Def"];
-        for run in runs.iter() {
-            let salt = generate_salt_multithread(&deployer, run, 6).unwrap();
-            let addr: [u8; 20] = calc_addr_with_bytes(&deployer, &salt.1);
-            assert_eq!(calc_addr(&deployer, salt.0.as_bytes()), addr);
-            assert!(hex::encode(addr).starts_with(&run.to_lowercase()));
-        }
+        assert_eq!(
+            generate_salt_custom(&deployer, "abc", 3, Charset::Numeric),
+            Err(Create3GenerateSaltError::SaltSpaceTooSmall)
+        );
+        assert_eq!(
+            generate_salt_custom(&deployer, "a", 0, Charset::Alphanumeric),
+            Err(Create3GenerateSaltError::SaltSpaceTooSmall)
+        );
+        assert_eq!(
+            generate_salt_custom(&deployer, "", 0, Charset::Numeric),
+            Ok((String::new(), Keccak256::digest("").into()))
+        );
     }
 
     #[test]
@@ -319,7 +3275,7 @@ ice is also great and would suffice.",
     fn should_generate_with_salt_prefix() {
         let deployer: Vec<u8> =
             hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
-        let runs = vec!["0", "00", "000", "abc", "123", "789", "Def"];
+        let runs = ["0", "00", "000", "abc", "123", "789", "Def"];
         let salt_prefix = "testpfx_";
         for run in runs.iter() {
             let (salt, digested_salt) =
@@ -337,11 +3293,69 @@ ice is also great and would suffice.",
         }
     }
 
+    #[test]
+    fn multithread_should_find_valid_salt_for_any_thread_count() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for thread_count in [1, 2, 3] {
+            for run in ["a", "5", "0f", "E1"] {
+                let (salt, digested_salt) = generate_salt_prefix_multithread(
+                    &deployer,
+                    "worker_",
+                    run,
+                    thread_count,
+                )
+                .unwrap();
+                assert!(salt.starts_with("worker_"));
+                assert_eq!(Keccak256::digest(&salt).as_slice(), digested_salt);
+                assert!(hex::encode(calc_addr(&deployer, salt.as_bytes()))
+                    .starts_with(&run.to_lowercase()));
+            }
+        }
+    }
+
+    #[test]
+    fn multithread_should_behave_the_same_for_every_prefix_form() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for thread_count in [0, 1, 4, 64] {
+            for run in ["", "0", "0xab", "0XAB", "dE", "7"] {
+                let (salt, digested_salt) = generate_salt_prefix_multithread(
+                    &deployer,
+                    "v1_",
+                    run,
+                    thread_count,
+                )
+                .unwrap();
+                assert_eq!(salt.len(), "v1_".len() + 7);
+                assert!(salt[3..].chars().all(|c| c.is_ascii_alphanumeric()));
+                assert_eq!(Keccak256::digest(&salt).as_slice(), digested_salt);
+                let expected = sanitize_hex_prefix(run).unwrap();
+                assert!(hex::encode(calc_addr(&deployer, salt.as_bytes()))
+                    .starts_with(&expected));
+            }
+            for (run, err) in [
+                ("xyz", Create3GenerateSaltError::PrefixNotHexEncoded),
+                (&"a".repeat(41), Create3GenerateSaltError::PrefixTooLong),
+            ] {
+                assert_eq!(
+                    generate_salt_prefix_multithread(
+                        &deployer,
+                        "v1_",
+                        run,
+                        thread_count
+                    ),
+                    Err(err)
+                );
+            }
+        }
+    }
+
     #[test]
     fn should_generate_multithread_with_salt_prefix() {
         let deployer: Vec<u8> =
             hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
-        let runs = vec!["0", "00", "000", "abc", "123", "789", "Def"];
+        let runs = ["0", "00", "000", "abc", "123", "789", "Def"];
         let salt_prefix = "testpfx_";
         for run in runs.iter() {
             let (salt, digested_salt) = generate_salt_prefix_multithread(
@@ -364,6 +3378,126 @@ ice is also great and would suffice.",
         }
     }
 
+    #[test]
+    fn matches_prefix_should_agree_with_hex_string_comparison() {
+        let deployer: Vec<u8> =
+            hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap();
+        let prefixes = ["", "b", "bf", "bff", "bff4", "bff47", "c", "bfe", "0"];
+        for salt in ["a", "b", "c", "d", "e"] {
+            let addr = calc_addr(&deployer, salt.as_bytes());
+            let addr_hex = hex::encode(addr);
+            for prefix in prefixes.iter() {
+                let nibbles = to_nibbles(prefix);
+                assert_eq!(
+                    matches_prefix(&addr, &nibbles),
+                    addr_hex.starts_with(prefix)
+                );
+            }
+            // every odd and even length slice of the address matches.
+            for len in 1..=20 {
+                let nibbles = to_nibbles(&addr_hex[..len]);
+                assert!(matches_prefix(&addr, &nibbles));
+            }
+        }
+    }
+
+    #[test]
+    fn matches_suffix_should_agree_with_hex_string_comparison() {
+        let deployer: Vec<u8> =
+            hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap();
+        for salt in ["a", "b", "c", "d", "e"] {
+            let addr = calc_addr(&deployer, salt.as_bytes());
+            let addr_hex = hex::encode(addr);
+            for len in 0..=40 {
+                let suffix = &addr_hex[40 - len..];
+                assert!(matches_suffix(&addr, &to_nibbles(suffix)));
+            }
+            let mut wrong = to_nibbles(&addr_hex[37..]);
+            wrong[0] ^= 1;
+            assert!(!matches_suffix(&addr, &wrong));
+            assert!(!matches_suffix(&addr, &[0; 41]));
+        }
+    }
+
+    #[test]
+    fn should_detect_degenerate_addresses() {
+        assert!(is_degenerate_addr(&[0x00; 20]));
+        assert!(is_degenerate_addr(&[0xff; 20]));
+        assert!(is_degenerate_addr(&[0x5a; 20]));
+        let mut addr = [0x00; 20];
+        addr[19] = 0x01;
+        assert!(!is_degenerate_addr(&addr));
+    }
+
+    #[test]
+    fn should_generate_non_degenerate_with_prefix() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for run in ["0", "ff", "abc"].iter() {
+            let (salt, digested_salt) =
+                generate_salt_non_degenerate(&deployer, run).unwrap();
+            let addr = calc_addr(&deployer, salt.as_bytes());
+            assert_eq!(addr, calc_addr_with_bytes(&deployer, &digested_salt));
+            assert!(hex::encode(addr).starts_with(run));
+            assert!(!is_degenerate_addr(&addr));
+        }
+    }
+
+    #[test]
+    fn should_digest_salt_without_hex_roundtrip() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let (salt, digested_salt) = generate_salt(&deployer, "a").unwrap();
+        let roundtrip =
+            hex::decode(hex::encode(Keccak256::digest(salt.clone()))).unwrap();
+        assert_eq!(digested_salt.as_slice(), roundtrip.as_slice());
+        assert_eq!(
+            digested_salt.as_slice(),
+            Keccak256::digest(salt).as_slice()
+        );
+    }
+
+    #[test]
+    fn sanitize_prefix_should_strip_leading_0x() {
+        assert_eq!(sanitize_hex_prefix("0xabc"), Ok("abc".to_owned()));
+        assert_eq!(sanitize_hex_prefix("0XAbC"), Ok("abc".to_owned()));
+        assert_eq!(sanitize_hex_prefix("0"), Ok("0".to_owned()));
+        assert_eq!(sanitize_hex_prefix("00"), Ok("00".to_owned()));
+        assert_eq!(sanitize_hex_prefix("0x"), Ok("".to_owned()));
+        assert_eq!(sanitize_hex_prefix(" 0x0 "), Ok("0".to_owned()));
+        assert_eq!(
+            sanitize_hex_prefix("0x0x1"),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let salt = generate_salt(&deployer, "0xA").unwrap();
+        assert!(hex::encode(calc_addr_with_bytes(&deployer, &salt.1))
+            .starts_with('a'));
+    }
+
+    #[test]
+    fn checksum_prefix_should_keep_case_unlike_hex_prefix() {
+        assert_eq!(sanitize_hex_prefix("0xDeAd"), Ok("dead".to_owned()));
+        assert_eq!(sanitize_checksum_prefix("0xDeAd"), Ok("DeAd".to_owned()));
+        assert_eq!(sanitize_checksum_prefix(" 0X0aB "), Ok("0aB".to_owned()));
+        assert_eq!(
+            sanitize_checksum_prefix("DeAdG"),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+        assert_eq!(
+            sanitize_checksum_prefix(&"A".repeat(21)),
+            Err(Create3GenerateSaltError::PrefixTooLong)
+        );
+        assert_eq!(sanitize_hex_prefix("0x"), Ok(String::new()));
+        for prefix in ["", "0x", " 0X "] {
+            assert_eq!(
+                sanitize_checksum_prefix(prefix),
+                Err(Create3GenerateSaltError::EmptyChecksumPrefix)
+            );
+        }
+    }
+
     #[test]
     fn generate_salt_should_error_if_prefix_is_greater_than_20_bytes() {
         let deployer = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".as_bytes();
@@ -377,7 +3511,7 @@ ice is also great and would suffice.",
     #[test]
     fn generate_salt_should_error_if_prefix_is_not_hex_encoded() {
         let deployer = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".as_bytes();
-        let runs = vec!["hey", "abcg", "0x123", "Ab45[", "lightning mcqueen"];
+        let runs = ["hey", "abcg", "0xx123", "Ab45[", "lightning mcqueen"];
         for run in runs.iter() {
             assert_eq!(
                 generate_salt(deployer, run),
@@ -401,7 +3535,7 @@ ice is also great and would suffice.",
     fn generate_salt_prefix_should_error_if_prefix_is_not_hex_encoded() {
         let deployer = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".as_bytes();
         let salt_prefix = "";
-        let runs = vec!["hey", "abcg", "0x123", "Ab45[", "lightning mcqueen"];
+        let runs = ["hey", "abcg", "0xx123", "Ab45[", "lightning mcqueen"];
         for run in runs.iter() {
             assert_eq!(
                 generate_salt_prefix(deployer, salt_prefix, run),
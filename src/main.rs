@@ -1,9 +1,46 @@
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand, ValueEnum};
 use create3::{
-    calc_addr, calc_addr_with_bytes, errors::Create3GenerateSaltError,
-    generate_salt, generate_salt_prefix,
+    address::Deployer,
+    batch::BatchProgress,
+    calc_addr, calc_addr_with_bytes,
+    checksum::to_checksum_address,
+    difficulty::success_probability_prefix_suffix,
+    errors::Create3GenerateSaltError,
+    generate_salt_cancellable,
+    miner::Create3Miner,
+    parse_salt_hash, salt_hash_to_bytes32_literal,
+    score::{count_leading_zero_nibbles, is_zero_address},
+    stats::MiningMetrics,
+    stream::{write_csv_line, write_ndjson_line, CSV_HEADER},
+    SaltResult,
 };
 use sha3::{Digest, Keccak256};
-use std::io::{self, Write};
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// consecutive duplicates after which a distinct batch is reported as stuck.
+const DUPLICATE_WARNING_THRESHOLD: u32 = 100;
+
+// leading zero nibbles from which an address looks like a burn address.
+const BURN_LIKE_ZERO_NIBBLES: u32 = 16;
+
+// time between two "still searching" status lines.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(2);
+
+// prefix the benchmark mines for; no address is expected to match it, so
+// every thread stays busy for the whole run.
+const BENCH_PREFIX: &str = "ffffffffffffffffffff";
+
+// where batch results and progress are kept, relative to the working dir.
+const BATCH_RESULTS_PATH: &str = "create3-batch-results.ndjson";
+const BATCH_PROGRESS_PATH: &str = "create3-batch.progress";
 
 /// reads a line from stdin and returns a trimmed string.
 fn read_input(prompt: &str) -> String {
@@ -14,8 +51,438 @@ fn read_input(prompt: &str) -> String {
     line.trim().to_owned()
 }
 
+/// calculates create3 addresses and mines vanity salts.
+///
+/// without a subcommand, an interactive menu is shown.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// skip repeated addresses in batch generation.
+    #[arg(long)]
+    distinct: bool,
+    /// continue an interrupted batch.
+    #[arg(long)]
+    resume_batch: bool,
+    /// also append every batch result to this file, as csv for '.csv' or
+    /// one json object per line for '.json'.
+    #[arg(long)]
+    out: Option<PathBuf>,
+    /// how results are printed.
+    #[arg(long, value_enum, default_value_t = Format::Text, global = true)]
+    format: Format,
+    /// reject mixed-case deployer addresses with a wrong eip-55 checksum.
+    #[arg(long, global = true)]
+    strict_checksum: bool,
+}
+
+/// output format for results.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// human readable lines.
+    Text,
+    /// one json object per result.
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// mine a salt whose address has the given prefix and suffix.
+    Mine {
+        /// create3 deployer address, with or without '0x'.
+        #[arg(long)]
+        deployer: String,
+        /// desired address prefix.
+        #[arg(long, default_value = "")]
+        prefix: String,
+        /// desired address suffix.
+        #[arg(long, default_value = "")]
+        suffix: String,
+        /// string to put in front of every random salt.
+        #[arg(long, default_value = "")]
+        salt_prefix: String,
+        /// number of threads to mine with; 0 uses every core.
+        #[arg(long, default_value_t = 0)]
+        threads: usize,
+        /// give up after this many salts, across all threads.
+        #[arg(long)]
+        max_attempts: Option<u64>,
+    },
+    /// calculate the address for a salt.
+    Calc {
+        /// create3 deployer address, with or without '0x'.
+        #[arg(long)]
+        deployer: String,
+        /// salt string (utf8); its keccak256 digest is used.
+        #[arg(long, required_unless_present = "salt_hash")]
+        salt: Option<String>,
+        /// raw 32-byte salt as 64 hex characters, with or without '0x',
+        /// used as is instead of hashing a salt string.
+        #[arg(long, conflicts_with = "salt", value_parser = parse_salt_hash)]
+        salt_hash: Option<[u8; 32]>,
+    },
+    /// measure the hash rate at 1, 2 and all threads.
+    Bench {
+        /// how long to mine at each thread count.
+        #[arg(long, default_value_t = 2.0)]
+        seconds: f64,
+    },
+}
+
+/// ctrl-c handling for long searches.
+///
+/// while a search runs, ctrl-c raises `stop` so the search returns
+/// `Cancelled` and results found so far can be kept; outside a search it
+/// exits right away, as usual.
+#[derive(Clone, Default)]
+struct Interrupt {
+    stop: Arc<AtomicBool>,
+    searching: Arc<AtomicBool>,
+}
+
+impl Interrupt {
+    /// installs the ctrl-c handler.
+    fn install() -> Self {
+        let interrupt = Interrupt::default();
+        let handler = interrupt.clone();
+        ctrlc::set_handler(move || {
+            if !handler.searching.load(Ordering::SeqCst) {
+                process::exit(130);
+            }
+            eprintln!("\x1b[31mstopping...\x1b[0m");
+            handler.stop.store(true, Ordering::SeqCst);
+        })
+        .expect("failed to install ctrl-c handler");
+        interrupt
+    }
+
+    /// runs a search that ctrl-c cancels through the given stop flag.
+    fn search<T>(&self, search: impl FnOnce(Arc<AtomicBool>) -> T) -> T {
+        self.stop.store(false, Ordering::SeqCst);
+        self.searching.store(true, Ordering::SeqCst);
+        let result = search(self.stop.clone());
+        self.searching.store(false, Ordering::SeqCst);
+        result
+    }
+}
+
 /// main entry point for the create3 address tool.
 fn main() {
+    let cli = Cli::parse();
+    let interrupt = Interrupt::install();
+    match cli.command {
+        Some(Command::Mine {
+            deployer,
+            prefix,
+            suffix,
+            salt_prefix,
+            threads,
+            max_attempts,
+        }) => {
+            let deployer = parse_deployer_arg(&deployer, cli.strict_checksum);
+            if let Some(max_attempts) = max_attempts {
+                warn_if_unlikely(&prefix, &suffix, max_attempts);
+            }
+            let result = interrupt.search(|stop| {
+                let miner = Create3Miner::new(deployer.as_bytes())
+                    .prefix(&prefix)
+                    .suffix(&suffix)
+                    .salt_prefix(&salt_prefix)
+                    .threads(threads)
+                    .cancel_on(stop);
+                let miner = match max_attempts {
+                    Some(max_attempts) => miner.max_attempts(max_attempts),
+                    None => miner,
+                };
+                mine_with_heartbeat(&miner, cli.format)
+            });
+            match result {
+                Ok(result) => {
+                    warn_if_burn_like(&result.address);
+                    print_result(
+                        cli.format,
+                        &result.address,
+                        Some(&result.salt),
+                        &result.salt_hash,
+                    )
+                }
+                Err(Create3GenerateSaltError::Cancelled) => {
+                    eprintln!("stopped before a match was found.");
+                    process::exit(130);
+                }
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        Some(Command::Calc {
+            deployer,
+            salt,
+            salt_hash,
+        }) => {
+            let deployer = parse_deployer_arg(&deployer, cli.strict_checksum);
+            let salt_hash = salt_hash.unwrap_or_else(|| {
+                Keccak256::digest(salt.as_deref().unwrap_or_default()).into()
+            });
+            let address = calc_addr_with_bytes(deployer.as_bytes(), &salt_hash);
+            print_result(cli.format, &address, salt.as_deref(), &salt_hash);
+        }
+        Some(Command::Bench { seconds }) => {
+            let duration = Duration::try_from_secs_f64(seconds)
+                .ok()
+                .filter(|d| !d.is_zero())
+                .unwrap_or_else(|| {
+                    eprintln!("error: seconds must be a positive number.");
+                    process::exit(1);
+                });
+            // the miner caps thread counts at the core count, so only
+            // measure the counts that actually differ.
+            let max = thread::available_parallelism().map_or(1, |n| n.get());
+            let mut thread_counts = vec![1, 2.min(max), max];
+            thread_counts.dedup();
+            for threads in thread_counts {
+                print_bench(cli.format, &bench(threads, duration));
+            }
+        }
+        None => {
+            let out = cli.out.map(|path| {
+                ResultsOut::open(&path).unwrap_or_else(|e| {
+                    eprintln!("error: {}: {}", path.display(), e);
+                    process::exit(1);
+                })
+            });
+            run_interactive(
+                cli.distinct,
+                cli.resume_batch,
+                cli.format,
+                cli.strict_checksum,
+                out,
+                &interrupt,
+            )
+        }
+    }
+}
+
+/// parses the `--deployer` argument, exiting with a usage error when it is
+/// invalid.
+///
+/// with `strict`, a mixed-case address must also match its eip-55 checksum.
+fn parse_deployer_arg(s: &str, strict: bool) -> Deployer {
+    let deployer = if strict {
+        Deployer::parse_strict(s)
+    } else {
+        s.parse()
+    };
+    deployer.unwrap_or_else(|e| {
+        Cli::command()
+            .error(
+                ErrorKind::ValueValidation,
+                format!("invalid value '{}' for '--deployer': {}", s, e),
+            )
+            .exit()
+    })
+}
+
+/// prints an address and its salt for the non-interactive commands.
+///
+/// the salt string is `None` when only the raw salt was given; the json
+/// output then has an empty salt.
+fn print_result(
+    format: Format,
+    address: &[u8; 20],
+    salt: Option<&str>,
+    salt_hash: &[u8; 32],
+) {
+    if format == Format::Json {
+        print_json(address, salt.unwrap_or_default(), salt_hash);
+        return;
+    }
+    println!("address: {}", to_checksum_address(address));
+    if let Some(salt) = salt {
+        println!("salt: {}", salt);
+    }
+    println!("salt hash: {}", salt_hash_to_bytes32_literal(salt_hash));
+}
+
+/// mines with a dim status line on stderr, redrawn every couple of seconds
+/// with the elapsed time and hash rate, so long searches don't look frozen.
+///
+/// the status line is left out for json output and when stderr isn't a
+/// terminal, and is cleared once the search ends.
+fn mine_with_heartbeat(
+    miner: &Create3Miner,
+    format: Format,
+) -> Result<SaltResult, Create3GenerateSaltError> {
+    if format == Format::Json || !io::stderr().is_terminal() {
+        return miner.mine();
+    }
+    let mut shown = false;
+    let result = miner.mine_with_progress(HEARTBEAT_INTERVAL, |stats| {
+        eprint!(
+            "\r\x1b[2K\x1b[2mstill searching: {}s elapsed, ~{:.0} attempts/s\x1b[0m",
+            stats.elapsed.as_secs(),
+            stats.hashes_per_sec
+        );
+        shown = true;
+    });
+    if shown {
+        eprint!("\r\x1b[2K");
+    }
+    result
+}
+
+/// mines for a prefix that won't match on `threads` threads and stops after
+/// `duration`, to measure the hash rate.
+fn bench(threads: usize, duration: Duration) -> MiningMetrics {
+    let stop = Arc::new(AtomicBool::new(false));
+    let attempts = Arc::new(AtomicU64::new(0));
+    let miner = Create3Miner::new(&[0; 20])
+        .prefix(BENCH_PREFIX)
+        .threads(threads)
+        .cancel_on(stop.clone())
+        .count_attempts(attempts.clone());
+    let start = Instant::now();
+    let result = miner.mine_with_progress(duration, |_| {
+        stop.store(true, Ordering::SeqCst);
+    });
+    let elapsed = start.elapsed();
+    if result != Err(Create3GenerateSaltError::Cancelled) {
+        eprintln!("error: benchmark ended early: {:?}", result);
+        process::exit(1);
+    }
+    // the workers count their last partial chunk as they stop, so this is
+    // every attempt of the run.
+    let attempts = attempts.load(Ordering::SeqCst);
+    if attempts == 0 {
+        eprintln!("error: no attempts were recorded; try more seconds.");
+        process::exit(1);
+    }
+    MiningMetrics {
+        attempts,
+        elapsed,
+        hashes_per_sec: attempts as f64 / elapsed.as_secs_f64(),
+        threads,
+    }
+}
+
+/// prints the hash rate measured at one thread count.
+fn print_bench(format: Format, metrics: &MiningMetrics) {
+    if format == Format::Json {
+        println!(
+            "{{\"threads\":{},\"attempts\":{},\"elapsed_secs\":{},\"hashes_per_sec\":{:.0}}}",
+            metrics.threads,
+            metrics.attempts,
+            metrics.elapsed.as_secs_f64(),
+            metrics.hashes_per_sec
+        );
+        return;
+    }
+    println!(
+        "{:>3} threads: {:.0} hashes/s",
+        metrics.threads, metrics.hashes_per_sec
+    );
+}
+
+/// warns on stderr when an attempt budget gives less than an even chance
+/// of finding a match; invalid prefixes are left for the search to report.
+fn warn_if_unlikely(prefix: &str, suffix: &str, max_attempts: u64) {
+    let Ok(chance) =
+        success_probability_prefix_suffix(prefix, suffix, max_attempts)
+    else {
+        return;
+    };
+    if chance < 0.5 {
+        eprintln!(
+            "\x1b[31mwarning: {} attempts give only a {:.2}% chance of finding a match.\x1b[0m",
+            max_attempts,
+            chance * 100.0
+        );
+    }
+}
+
+/// warns on stderr when a generated address is the zero address or looks
+/// like a burn address; deploying there is almost always a mistake.
+fn warn_if_burn_like(address: &[u8; 20]) {
+    if is_zero_address(address) {
+        eprintln!("\x1b[31mwarning: this is the zero address.\x1b[0m");
+    } else if count_leading_zero_nibbles(address) >= BURN_LIKE_ZERO_NIBBLES {
+        eprintln!(
+            "\x1b[31mwarning: {} has {} leading zero nibbles and looks like a burn address.\x1b[0m",
+            to_checksum_address(address),
+            count_leading_zero_nibbles(address)
+        );
+    }
+}
+
+/// file batch results are appended to as they are found, so an interrupted
+/// run keeps what it already mined.
+struct ResultsOut {
+    file: File,
+    csv: bool,
+}
+
+impl ResultsOut {
+    /// opens a results file for appending, picking the format from its
+    /// extension and writing the csv header to a new csv file.
+    fn open(path: &Path) -> io::Result<Self> {
+        let csv = match path.extension().and_then(|e| e.to_str()) {
+            Some("csv") => true,
+            Some("json") => false,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "expected a .csv or .json file",
+                ))
+            }
+        };
+        let mut file =
+            OpenOptions::new().create(true).append(true).open(path)?;
+        if csv && file.metadata()?.len() == 0 {
+            writeln!(file, "{}", CSV_HEADER)?;
+        }
+        Ok(ResultsOut { file, csv })
+    }
+
+    /// appends one result.
+    fn append(
+        &mut self,
+        address: &[u8; 20],
+        salt: &str,
+        salt_hash: &[u8; 32],
+    ) -> io::Result<()> {
+        if self.csv {
+            write_csv_line(&mut self.file, address, salt, salt_hash)
+        } else {
+            write_ndjson_line(&mut self.file, address, salt, salt_hash)
+        }
+    }
+}
+
+/// prints an address and its salt as a single line of json.
+fn print_json(address: &[u8; 20], salt: &str, salt_hash: &[u8; 32]) {
+    write_ndjson_line(&mut io::stdout(), address, salt, salt_hash)
+        .expect("failed to write to stdout");
+}
+
+/// runs the interactive menu.
+///
+/// arguments:
+/// - distinct: skip repeated addresses in batch generation.
+/// - resume: continue an interrupted batch.
+/// - format: how the single-address and vanity-salt results are printed.
+/// - strict_checksum: check the eip-55 checksum of mixed-case deployers.
+/// - out: extra file batch results are appended to.
+/// - interrupt: lets ctrl-c stop a running search.
+fn run_interactive(
+    distinct: bool,
+    resume: bool,
+    format: Format,
+    strict_checksum: bool,
+    mut out: Option<ResultsOut>,
+    interrupt: &Interrupt,
+) {
     println!("\x1b[32m=========================\x1b[0m");
     println!("\x1b[32m=  create3 address tool  =\x1b[0m");
     println!("\x1b[32m=========================\x1b[0m");
@@ -33,9 +500,17 @@ fn main() {
         match choice.as_str() {
             "1" => {
                 // generate create3 address using user-provided salt.
-                let deployer = request_deployer_address();
+                let deployer = request_deployer_address(strict_checksum);
                 let salt = read_input("\x1b[36menter salt (utf8):\x1b[0m ");
                 let address = calc_addr(&deployer, salt.as_bytes());
+                if format == Format::Json {
+                    print_json(
+                        &address,
+                        &salt,
+                        &Keccak256::digest(&salt).into(),
+                    );
+                    break;
+                }
                 println!(
                     "\x1b[32mcreate3 address:\x1b[0m {}",
                     to_checksum_address(&address)
@@ -44,19 +519,25 @@ fn main() {
             }
             "2" => {
                 // generate salt that yields an address with the given prefix.
-                let deployer = request_deployer_address();
-                let mut prefix = String::new();
+                let deployer = request_deployer_address(strict_checksum);
+                let mut prefix;
                 let salt;
                 loop {
                     prefix = read_input(
                         "\x1b[36menter prefix (without '0x' prefix):\x1b[0m ",
                     );
-                    // use generate_salt to validate prefix.
-                    match generate_salt(&deployer, &prefix) {
+                    // the search also validates the prefix.
+                    match interrupt.search(|stop| {
+                        let miner = Create3Miner::new(&deployer)
+                            .prefix(&prefix)
+                            .cancel_on(stop);
+                        mine_with_heartbeat(&miner, format)
+                    }) {
                         Ok(s) => {
-                            salt = s;
+                            salt = (s.salt, s.salt_hash);
                             break;
                         }
+                        Err(Create3GenerateSaltError::Cancelled) => return,
                         Err(Create3GenerateSaltError::PrefixNotHexEncoded) => {
                             println!(
                                 "\x1b[36minput was not hex encoded.\x1b[0m"
@@ -67,41 +548,52 @@ fn main() {
                                 "\x1b[36mprefix was too long (over 20 characters).\x1b[0m"
                             );
                         }
+                        Err(e) => println!("\x1b[36m{}\x1b[0m", e),
                     }
                 }
                 let vanity_addr = calc_addr_with_bytes(&deployer, &salt.1);
+                warn_if_burn_like(&vanity_addr);
+                if format == Format::Json {
+                    print_json(&vanity_addr, &salt.0, &salt.1);
+                    break;
+                }
                 println!(
                     "\x1b[32mvanity address:\x1b[0m {}",
                     to_checksum_address(&vanity_addr)
                 );
                 println!("\x1b[32msalt string:\x1b[0m {}", salt.0);
                 println!(
-                    "\x1b[32mhashed salt for prefix {}:\x1b[0m 0x{}",
+                    "\x1b[32mhashed salt for prefix {}:\x1b[0m {}",
                     prefix,
-                    hex::encode(salt.1)
+                    salt_hash_to_bytes32_literal(&salt.1)
                 );
                 break;
             }
             "3" => {
                 // generate salt with a salt prefix to yield a vanity address.
-                let deployer = request_deployer_address();
+                let deployer = request_deployer_address(strict_checksum);
                 let salt_prefix =
                     read_input("\x1b[36menter salt prefix (utf8):\x1b[0m ");
-                let mut prefix = String::new();
+                let mut prefix;
                 let generated;
                 let vanity_addr;
                 loop {
                     prefix = read_input(
                         "\x1b[36menter address prefix (without '0x' prefix):\x1b[0m ",
                     );
-                    match generate_salt_prefix(&deployer, &salt_prefix, &prefix)
-                    {
+                    match interrupt.search(|stop| {
+                        let miner = Create3Miner::new(&deployer)
+                            .prefix(&prefix)
+                            .salt_prefix(&salt_prefix)
+                            .cancel_on(stop);
+                        mine_with_heartbeat(&miner, format)
+                    }) {
                         Ok(s) => {
-                            generated = s;
-                            vanity_addr =
-                                calc_addr_with_bytes(&deployer, &generated.1);
+                            vanity_addr = s.address;
+                            generated = (s.salt, s.salt_hash);
                             break;
                         }
+                        Err(Create3GenerateSaltError::Cancelled) => return,
                         Err(Create3GenerateSaltError::PrefixNotHexEncoded) => {
                             println!(
                                 "\x1b[36minput was not hex encoded.\x1b[0m"
@@ -112,8 +604,14 @@ fn main() {
                                 "\x1b[36mprefix was too long (over 20 characters).\x1b[0m"
                             );
                         }
+                        Err(e) => println!("\x1b[36m{}\x1b[0m", e),
                     }
                 }
+                warn_if_burn_like(&vanity_addr);
+                if format == Format::Json {
+                    print_json(&vanity_addr, &generated.0, &generated.1);
+                    break;
+                }
                 println!(
                     "\x1b[32mvanity address:\x1b[0m {}",
                     to_checksum_address(&vanity_addr)
@@ -123,22 +621,25 @@ fn main() {
                     salt_prefix, generated.0
                 );
                 println!(
-                    "\x1b[32mhashed salt :\x1b[0m 0x{}",
-                    hex::encode(&generated.1)
+                    "\x1b[32mhashed salt :\x1b[0m {}",
+                    salt_hash_to_bytes32_literal(&generated.1)
                 );
                 break;
             }
             "4" => {
                 // batch generate salts for a given prefix.
-                let deployer = request_deployer_address();
-                let mut prefix = String::new();
+                let deployer = request_deployer_address(strict_checksum);
+                let mut prefix;
                 loop {
                     prefix = read_input(
                         "\x1b[36menter prefix (without '0x' prefix):\x1b[0m ",
                     );
-                    // validate prefix using generate_salt.
-                    match generate_salt(&deployer, &prefix) {
+                    // validate prefix by mining a first salt.
+                    match interrupt.search(|stop| {
+                        generate_salt_cancellable(&deployer, &prefix, 1, stop)
+                    }) {
                         Ok(_) => break,
+                        Err(Create3GenerateSaltError::Cancelled) => return,
                         Err(Create3GenerateSaltError::PrefixNotHexEncoded) => {
                             println!(
                                 "\x1b[36minput was not hex encoded.\x1b[0m"
@@ -149,27 +650,92 @@ fn main() {
                                 "\x1b[36mprefix was too long (over 20 characters).\x1b[0m"
                             );
                         }
+                        Err(e) => println!("\x1b[36m{}\x1b[0m", e),
                     }
                 }
                 let num_str = read_input(
                     "\x1b[36menter number of addresses to generate:\x1b[0m ",
                 );
                 let num: u32 = num_str.parse().expect("invalid number entered");
-                for i in 1..=num {
-                    let salt = generate_salt(&deployer, &prefix).unwrap();
+                let Some((mut progress, mut results)) =
+                    open_batch(&deployer, &prefix, resume)
+                else {
+                    break;
+                };
+                // a resumed batch must not repeat addresses it already
+                // wrote; a new batch starts from an empty results file.
+                let mut seen: HashSet<[u8; 20]> = if distinct {
+                    progress
+                        .load_result_addresses()
+                        .expect("failed to read batch results")
+                } else {
+                    HashSet::new()
+                };
+                let mut duplicates: u32 = 0;
+                let mut i = progress.completed_count as u32;
+                while i < num {
+                    let salt = match interrupt.search(|stop| {
+                        generate_salt_cancellable(&deployer, &prefix, 1, stop)
+                    }) {
+                        Ok(salt) => salt,
+                        Err(Create3GenerateSaltError::Cancelled) => {
+                            // every result so far is already flushed and
+                            // the progress file is kept for resuming.
+                            println!(
+                                "\x1b[31mstopped after {} results; run with --resume-batch to continue.\x1b[0m",
+                                i
+                            );
+                            return;
+                        }
+                        Err(e) => panic!("{}", e),
+                    };
                     let vanity_addr = calc_addr_with_bytes(&deployer, &salt.1);
+                    if distinct && !seen.insert(vanity_addr) {
+                        duplicates += 1;
+                        if duplicates == DUPLICATE_WARNING_THRESHOLD {
+                            println!(
+                                "\x1b[31mwarning: {} duplicates in a row; {} distinct addresses may be slow or impossible to produce.\x1b[0m",
+                                duplicates, num
+                            );
+                        }
+                        continue;
+                    }
+                    duplicates = 0;
+                    i += 1;
                     println!("\x1b[32mresult {}:\x1b[0m", i);
+                    warn_if_burn_like(&vanity_addr);
                     println!("  salt string: {}", salt.0);
                     println!(
                         "  vanity address: {}",
                         to_checksum_address(&vanity_addr)
                     );
                     println!(
-                        "  hashed salt for prefix {}: 0x{}",
+                        "  hashed salt for prefix {}: {}",
                         prefix,
-                        hex::encode(salt.1)
+                        salt_hash_to_bytes32_literal(&salt.1)
                     );
+                    write_ndjson_line(
+                        &mut results,
+                        &vanity_addr,
+                        &salt.0,
+                        &salt.1,
+                    )
+                    .expect("failed to write batch results");
+                    if let Some(out) = &mut out {
+                        out.append(&vanity_addr, &salt.0, &salt.1)
+                            .expect("failed to write results to --out file");
+                    }
+                    progress.completed_count = i as u64;
+                    progress
+                        .save(Path::new(BATCH_PROGRESS_PATH))
+                        .expect("failed to save batch progress");
                 }
+                // the batch is complete, so there is nothing left to resume.
+                let _ = fs::remove_file(BATCH_PROGRESS_PATH);
+                println!(
+                    "\x1b[32mresults written to {}\x1b[0m",
+                    progress.results_path.display()
+                );
                 break;
             }
             _ => {
@@ -179,43 +745,72 @@ fn main() {
     }
 }
 
+/// sets up progress tracking and the results file for a batch.
+///
+/// when resuming, the saved progress must match the requested deployer and
+/// prefix, and results are appended; otherwise a fresh results file is
+/// started.
+///
+/// returns: the batch progress and results file, or None if the saved
+/// progress is unusable.
+fn open_batch(
+    deployer: &[u8],
+    prefix: &str,
+    resume: bool,
+) -> Option<(BatchProgress, File)> {
+    let requested =
+        BatchProgress::new(deployer, prefix, Path::new(BATCH_RESULTS_PATH));
+    let saved = if resume {
+        match BatchProgress::load(Path::new(BATCH_PROGRESS_PATH)) {
+            Ok(Some(saved)) if saved.is_compatible(&requested) => Some(saved),
+            Ok(Some(_)) => {
+                println!("\x1b[31m{} is for a different deployer or prefix; not resuming.\x1b[0m", BATCH_PROGRESS_PATH);
+                return None;
+            }
+            Ok(None) => {
+                println!(
+                    "\x1b[36mno batch to resume, starting a new one.\x1b[0m"
+                );
+                None
+            }
+            Err(e) => {
+                println!("\x1b[31m{}\x1b[0m", e);
+                return None;
+            }
+        }
+    } else {
+        None
+    };
+    let results = match saved {
+        Some(_) => OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(BATCH_RESULTS_PATH),
+        None => File::create(BATCH_RESULTS_PATH),
+    };
+    match results {
+        Ok(results) => Some((saved.unwrap_or(requested), results)),
+        Err(e) => {
+            println!("\x1b[31m{}: {}\x1b[0m", BATCH_RESULTS_PATH, e);
+            None
+        }
+    }
+}
+
 /// reads and validates the deployer address from stdin.
-/// the address should be in hex (without '0x') and 40 chars long.
-fn request_deployer_address() -> Vec<u8> {
+/// the address should be in hex (without '0x') and 40 chars long; with
+/// `strict`, a mixed-case address must also match its eip-55 checksum.
+fn request_deployer_address(strict: bool) -> Vec<u8> {
     loop {
         let input = read_input("\x1b[36menter deployer address:\x1b[0m ");
-        let addr = input.trim_start_matches("0x");
-        if !addr.chars().all(|c| c.is_ascii_hexdigit()) {
-            println!("\x1b[36minput was not hex encoded.\x1b[0m");
-            continue;
-        } else if addr.len() != 40 {
-            println!(
-                "\x1b[36minput has an incorrect length (expected 40).\x1b[0m"
-            );
-            continue;
-        }
-        return hex::decode(addr).unwrap();
-    }
-}
-
-/// converts a 20-byte ethereum address into its eip-55 checksummed form.
-/// the address is converted to lowercase hex, hashed with keccak256, and
-/// characters are uppercased based on the hash.
-fn to_checksum_address(address: &[u8]) -> String {
-    let address_hex = hex::encode(address);
-    let mut hasher = Keccak256::new();
-    hasher.update(address_hex.as_bytes());
-    let hash = hasher.finalize();
-    let hash_hex = hex::encode(hash);
-    let mut result = String::from("0x");
-    for (i, ch) in address_hex.chars().enumerate() {
-        let hash_digit = hash_hex.chars().nth(i).unwrap();
-        let hash_val = hash_digit.to_digit(16).unwrap();
-        if hash_val >= 8 {
-            result.push(ch.to_ascii_uppercase());
+        let deployer = if strict {
+            Deployer::parse_strict(&input)
         } else {
-            result.push(ch);
+            input.parse::<Deployer>()
+        };
+        match deployer {
+            Ok(deployer) => return deployer.as_bytes().to_vec(),
+            Err(e) => println!("\x1b[36m{}\x1b[0m", e),
         }
     }
-    result
 }
@@ -0,0 +1,507 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::rngs::SmallRng;
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    address_nibbles, calc_addr_with_bytes, check_deployer, check_salt_prefix,
+    contains_nibbles, errors::Create3GenerateSaltError, matches_pattern,
+    matches_prefix, matches_suffix, parse_pattern, random_salt,
+    sanitize_hex_prefix, search_parallel, search_rng, stats::MiningStats,
+    to_nibbles, Create3, SaltResult, SearchControl,
+};
+
+/// builder combining the salt mining options.
+///
+/// an address must satisfy every match option that is set, and every
+/// salt starts with the salt prefix whichever options are used.
+///
+/// ```
+/// # use create3::miner::Create3Miner;
+/// # let deployer = [0u8; 20];
+/// let result = Create3Miner::new(&deployer)
+///     .prefix("de")
+///     .salt_prefix("v2_")
+///     .threads(2)
+///     .max_attempts(10_000_000)
+///     .mine();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Create3Miner {
+    deployer: Vec<u8>,
+    prefix: String,
+    suffix: String,
+    contains: Vec<String>,
+    pattern: String,
+    salt_prefix: String,
+    threads: usize,
+    max_attempts: Option<u64>,
+    stop: Option<Arc<AtomicBool>>,
+    attempts: Option<Arc<AtomicU64>>,
+}
+
+impl Create3Miner {
+    /// creates a miner with no prefix, no salt prefix, a single thread and
+    /// no attempt limit.
+    ///
+    /// arguments:
+    /// - deployer: create3 deployer address (bytes).
+    pub fn new(deployer: &[u8]) -> Self {
+        Create3Miner {
+            deployer: deployer.to_owned(),
+            prefix: String::new(),
+            suffix: String::new(),
+            contains: Vec::new(),
+            pattern: String::new(),
+            salt_prefix: String::new(),
+            threads: 1,
+            max_attempts: None,
+            stop: None,
+            attempts: None,
+        }
+    }
+
+    /// sets the desired address prefix (without '0x').
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_owned();
+        self
+    }
+
+    /// sets the desired address suffix.
+    pub fn suffix(mut self, suffix: &str) -> Self {
+        self.suffix = suffix.to_owned();
+        self
+    }
+
+    /// requires the address to contain at least one of these hex strings
+    /// anywhere in it; an empty list drops the requirement.
+    pub fn contains_any(mut self, needles: &[&str]) -> Self {
+        self.contains = needles.iter().map(|n| (*n).to_owned()).collect();
+        self
+    }
+
+    /// sets a nibble pattern of hex digits and '?' wildcards, anchored at
+    /// the start of the address, e.g. "de?d".
+    pub fn pattern(mut self, pattern: &str) -> Self {
+        self.pattern = pattern.to_owned();
+        self
+    }
+
+    /// sets a string to put in front of every random salt, at most
+    /// `MAX_SALT_PREFIX_LEN` bytes long.
+    pub fn salt_prefix(mut self, salt_prefix: &str) -> Self {
+        self.salt_prefix = salt_prefix.to_owned();
+        self
+    }
+
+    /// sets the number of threads to mine with (capped at the core count),
+    /// or 0 to use every available core.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// limits the total number of salts tried across all threads.
+    pub fn max_attempts(mut self, max_attempts: u64) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// stops mining once `stop` is set, e.g. from a ctrl-c handler.
+    pub fn cancel_on(mut self, stop: Arc<AtomicBool>) -> Self {
+        self.stop = Some(stop);
+        self
+    }
+
+    /// adds every salt tried to `attempts`, which can be read while mining
+    /// and holds the exact count once mining returns, whether or not a
+    /// match was found.
+    pub fn count_attempts(mut self, attempts: Arc<AtomicU64>) -> Self {
+        self.attempts = Some(attempts);
+        self
+    }
+
+    /// mines a salt with the configured options.
+    ///
+    /// returns: the first matching salt, a `Cancelled` error if the stop
+    /// flag was set first, or a `NotFound` error once the attempt limit is
+    /// used up.
+    pub fn mine(&self) -> Result<SaltResult, Create3GenerateSaltError> {
+        self.search(None, |_| {})
+    }
+
+    /// mines a salt with the configured options, reporting progress every
+    /// `interval` while the search runs.
+    ///
+    /// `on_progress` is called from the calling thread and never with stats
+    /// taken after a match has been found or the search was cancelled.
+    ///
+    /// returns: the same as `mine`.
+    pub fn mine_with_progress(
+        &self,
+        interval: Duration,
+        on_progress: impl FnMut(MiningStats),
+    ) -> Result<SaltResult, Create3GenerateSaltError> {
+        self.search(Some(interval), on_progress)
+    }
+
+    /// runs the shared search with a matcher combining every option set.
+    fn search(
+        &self,
+        interval: Option<Duration>,
+        mut on_progress: impl FnMut(MiningStats),
+    ) -> Result<SaltResult, Create3GenerateSaltError> {
+        let prefix = to_nibbles(&sanitize_hex_prefix(&self.prefix)?);
+        let suffix = to_nibbles(&sanitize_hex_prefix(&self.suffix)?);
+        let needles = self
+            .contains
+            .iter()
+            .map(|needle| Ok(to_nibbles(&sanitize_hex_prefix(needle)?)))
+            .collect::<Result<Vec<_>, Create3GenerateSaltError>>()?;
+        let pattern = parse_pattern(&self.pattern)?;
+        check_deployer(&self.deployer)?;
+        check_salt_prefix(&self.salt_prefix)?;
+        let stop = self.stop.clone().unwrap_or_default();
+        let attempts = self.attempts.clone().unwrap_or_default();
+        let counted_before = attempts.load(Ordering::Relaxed);
+        let (salt, salt_hash) = search_parallel(
+            &self.deployer,
+            &self.salt_prefix,
+            self.threads,
+            SearchControl {
+                stop: &stop,
+                max_attempts: self.max_attempts.unwrap_or(u64::MAX),
+                attempts: &attempts,
+                progress: interval.map(|interval| {
+                    (interval, &mut on_progress as &mut dyn FnMut(MiningStats))
+                }),
+            },
+            |_, address| {
+                matches_prefix(address, &prefix)
+                    && matches_suffix(address, &suffix)
+                    && matches_pattern(address, &pattern)
+                    && (needles.is_empty() || {
+                        let nibbles = address_nibbles(address);
+                        needles
+                            .iter()
+                            .any(|needle| contains_nibbles(&nibbles, needle))
+                    })
+            },
+        )?;
+        Ok(SaltResult {
+            address: calc_addr_with_bytes(&self.deployer, &salt_hash),
+            salt,
+            salt_hash,
+            attempts: attempts.load(Ordering::Relaxed) - counted_before,
+        })
+    }
+}
+
+/// lazily yields salts whose addresses start with a prefix, searching
+/// single-threaded and resuming where it left off on each call to `next`.
+///
+/// created by `Create3::iter_matches`.
+#[derive(Clone, Debug)]
+pub struct MatchIterator {
+    create3: Create3,
+    prefix: Vec<u8>,
+    rng: SmallRng,
+}
+
+impl MatchIterator {
+    pub(crate) fn new(create3: Create3, prefix_nibbles: Vec<u8>) -> Self {
+        MatchIterator {
+            create3,
+            prefix: prefix_nibbles,
+            rng: search_rng(),
+        }
+    }
+}
+
+impl Iterator for MatchIterator {
+    type Item = SaltResult;
+
+    fn next(&mut self) -> Option<SaltResult> {
+        let mut attempts: u64 = 0;
+        loop {
+            let salt = random_salt(&mut self.rng, 10);
+            let salt_hash: [u8; 32] = Keccak256::digest(&salt).into();
+            let address = self.create3.calc_addr_with_bytes(&salt_hash);
+            attempts += 1;
+            if matches_prefix(&address, &self.prefix) {
+                return Some(SaltResult {
+                    salt,
+                    salt_hash,
+                    address,
+                    attempts,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::{
+        calc_addr, difficulty::expected_attempts,
+        errors::Create3GenerateSaltError, miner::Create3Miner, Create3,
+        PANICKING_DEPLOYER,
+    };
+
+    #[test]
+    fn should_iterate_distinct_matches() {
+        let deployer: [u8; 20] =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let miner = Create3::new(&deployer);
+        let results: Vec<_> =
+            miner.iter_matches("aB").unwrap().take(2).collect();
+        assert_eq!(results.len(), 2);
+        assert_ne!(results[0].salt, results[1].salt);
+        for result in results.iter() {
+            assert_eq!(
+                calc_addr(&deployer, result.salt.as_bytes()),
+                result.address
+            );
+            assert!(hex::encode(result.address).starts_with("ab"));
+        }
+    }
+
+    #[test]
+    fn iter_matches_should_error_on_invalid_prefix() {
+        let miner = Create3::new(&[0; 20]);
+        assert_eq!(
+            miner.iter_matches("xyz").unwrap_err(),
+            Create3GenerateSaltError::PrefixNotHexEncoded
+        );
+    }
+
+    #[test]
+    fn miner_should_mine_with_prefix_and_salt_prefix() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for threads in [1, 4] {
+            let result = Create3Miner::new(&deployer)
+                .prefix("aB")
+                .salt_prefix("v2_")
+                .threads(threads)
+                .mine()
+                .unwrap();
+            assert!(result.salt.starts_with("v2_"));
+            assert_eq!(
+                calc_addr(&deployer, result.salt.as_bytes()),
+                result.address
+            );
+            assert!(hex::encode(result.address).starts_with("ab"));
+        }
+    }
+
+    #[test]
+    fn miner_should_keep_salt_prefix_in_every_match_mode() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        type Check = fn(&str) -> bool;
+        let modes: [(Create3Miner, Check); 4] = [
+            (Create3Miner::new(&deployer).prefix("a"), |a| {
+                a.starts_with('a')
+            }),
+            (Create3Miner::new(&deployer).suffix("b"), |a| {
+                a.ends_with('b')
+            }),
+            (
+                Create3Miner::new(&deployer).contains_any(&["cafe", "c0"]),
+                |a| a.contains("cafe") || a.contains("c0"),
+            ),
+            (Create3Miner::new(&deployer).pattern("?d"), |a| {
+                &a[1..2] == "d"
+            }),
+        ];
+        for (miner, matches) in modes {
+            let result = miner.salt_prefix("ns/").threads(2).mine().unwrap();
+            assert!(result.salt.starts_with("ns/"));
+            assert_eq!(
+                calc_addr(&deployer, result.salt.as_bytes()),
+                result.address
+            );
+            assert!(matches(&hex::encode(result.address)));
+        }
+        assert_eq!(
+            Create3Miner::new(&deployer).contains_any(&["xyz"]).mine(),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+        assert_eq!(
+            Create3Miner::new(&deployer).pattern("g").mine(),
+            Err(Create3GenerateSaltError::InvalidPatternCharacter)
+        );
+    }
+
+    #[test]
+    fn miner_should_mine_with_prefix_and_suffix() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let result = Create3Miner::new(&deployer)
+            .prefix("a")
+            .suffix("Be")
+            .threads(2)
+            .mine()
+            .unwrap();
+        let address = hex::encode(result.address);
+        assert!(address.starts_with('a'));
+        assert!(address.ends_with("be"));
+        assert_eq!(
+            Create3Miner::new(&deployer).suffix("xyz").mine(),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+
+    #[test]
+    fn miner_should_report_progress_while_mining() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let mut reports = Vec::new();
+        // a prefix this long won't match within the attempt limit, so the
+        // search runs long enough for several reports.
+        let result = Create3Miner::new(&deployer)
+            .prefix("ffffffffffffffff")
+            .threads(2)
+            .max_attempts(50_000)
+            .mine_with_progress(Duration::from_millis(20), |stats| {
+                reports.push(stats)
+            });
+        assert_eq!(
+            result,
+            Err(Create3GenerateSaltError::NotFound { attempts: 50_000 })
+        );
+        assert!(!reports.is_empty());
+        for pair in reports.windows(2) {
+            assert!(pair[0].attempts <= pair[1].attempts);
+            assert!(pair[0].elapsed < pair[1].elapsed);
+        }
+        assert!(reports.iter().all(|stats| stats.attempts <= 50_000));
+    }
+
+    #[test]
+    fn miner_should_stop_when_cancelled() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let stop = Arc::new(AtomicBool::new(false));
+        let canceller = {
+            let stop = stop.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                stop.store(true, Ordering::Relaxed);
+            })
+        };
+        let result = Create3Miner::new(&deployer)
+            .prefix("ffffffffffffffff")
+            .threads(2)
+            .cancel_on(stop)
+            .mine();
+        canceller.join().unwrap();
+        assert_eq!(result, Err(Create3GenerateSaltError::Cancelled));
+    }
+
+    #[test]
+    fn miner_should_count_attempts_when_cancelled() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let stop = Arc::new(AtomicBool::new(false));
+        let attempts = Arc::new(AtomicU64::new(0));
+        let mut reported = 0;
+        let result = Create3Miner::new(&deployer)
+            .prefix("ffffffffffffffff")
+            .threads(2)
+            .cancel_on(stop.clone())
+            .count_attempts(attempts.clone())
+            .mine_with_progress(Duration::from_millis(1), |stats| {
+                reported = stats.attempts;
+                stop.store(true, Ordering::Relaxed);
+            });
+        assert_eq!(result, Err(Create3GenerateSaltError::Cancelled));
+        // the workers' unfinished chunks are counted as they stop.
+        assert!(attempts.load(Ordering::Relaxed) >= reported);
+        assert!(attempts.load(Ordering::Relaxed) > 0);
+
+        let before = attempts.load(Ordering::Relaxed);
+        let result = Create3Miner::new(&deployer)
+            .prefix("abcdef012345")
+            .max_attempts(3_000)
+            .count_attempts(attempts.clone())
+            .mine();
+        assert_eq!(
+            result,
+            Err(Create3GenerateSaltError::NotFound { attempts: 3_000 })
+        );
+        assert_eq!(attempts.load(Ordering::Relaxed), before + 3_000);
+    }
+
+    #[test]
+    fn miner_should_error_when_a_worker_panics() {
+        assert_eq!(
+            Create3Miner::new(&PANICKING_DEPLOYER)
+                .prefix("abcdef")
+                .threads(3)
+                .mine(),
+            Err(Create3GenerateSaltError::WorkerPanicked)
+        );
+    }
+
+    #[test]
+    fn miner_should_default_to_any_address() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let result = Create3Miner::new(&deployer).mine().unwrap();
+        assert_eq!(result.salt.len(), 7);
+        assert_eq!(
+            calc_addr(&deployer, result.salt.as_bytes()),
+            result.address
+        );
+    }
+
+    #[test]
+    fn miner_should_error_when_attempts_are_exhausted() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        assert_eq!(
+            Create3Miner::new(&deployer)
+                .prefix("abcdef012345")
+                .threads(3)
+                .max_attempts(5_000)
+                .mine(),
+            Err(Create3GenerateSaltError::NotFound { attempts: 5_000 })
+        );
+        assert_eq!(
+            Create3Miner::new(&deployer).prefix("xyz").mine(),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+
+    #[test]
+    fn miner_should_report_attempts_near_expected() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let expected = expected_attempts("a").unwrap();
+        let runs = 32;
+        let mut total: u64 = 0;
+        for _ in 0..runs {
+            let result = Create3Miner::new(&deployer)
+                .prefix("a")
+                .threads(2)
+                .mine()
+                .unwrap();
+            assert!(result.attempts >= 1);
+            total += result.attempts;
+        }
+        let mean = total as f64 / runs as f64;
+        assert!(mean > expected / 10.0 && mean < expected * 10.0);
+    }
+}
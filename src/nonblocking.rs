@@ -0,0 +1,82 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::{
+    errors::Create3GenerateSaltError, miner::Create3Miner,
+    resolve_thread_count, SaltResult,
+};
+
+/// raises the stop flag when dropped, so a search ends with its future.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// generates a salt for a given prefix without blocking the async runtime.
+///
+/// the search runs on tokio's blocking pool; dropping the returned future,
+/// e.g. when a request is aborted or times out, stops the search.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: the matching salt and its address.
+pub async fn generate_salt_async(
+    deployer: &[u8],
+    prefix: &str,
+    thread_count: usize,
+) -> Result<SaltResult, Create3GenerateSaltError> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let _cancel = CancelOnDrop(stop.clone());
+    let miner = Create3Miner::new(deployer)
+        .prefix(prefix)
+        .threads(resolve_thread_count(thread_count))
+        .cancel_on(stop);
+    tokio::task::spawn_blocking(move || miner.mine())
+        .await
+        .map_err(|_| Create3GenerateSaltError::WorkerPanicked)?
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        calc_addr, errors::Create3GenerateSaltError,
+        nonblocking::generate_salt_async,
+    };
+
+    #[tokio::test]
+    async fn should_generate_salt_async() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let result = generate_salt_async(&deployer, "aB", 2).await.unwrap();
+        assert_eq!(
+            calc_addr(&deployer, result.salt.as_bytes()),
+            result.address
+        );
+        assert!(hex::encode(result.address).starts_with("ab"));
+        assert_eq!(
+            generate_salt_async(&deployer, "xyz", 2).await,
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+
+    // the runtime waits for blocking tasks on shutdown, so this only
+    // finishes if dropping the future stops the search.
+    #[tokio::test]
+    async fn dropping_the_future_should_stop_the_search() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let search = generate_salt_async(&deployer, "ffffffffffffffff", 1);
+        assert!(tokio::time::timeout(Duration::from_millis(50), search)
+            .await
+            .is_err());
+    }
+}
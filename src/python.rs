@@ -0,0 +1,107 @@
+//! python bindings, built as an extension module with e.g.
+//! `maturin build --release`.
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::errors::Create3GenerateSaltError;
+
+create_exception!(
+    create3,
+    Create3Error,
+    PyValueError,
+    "raised when create3 rejects its input or a search fails."
+);
+
+impl From<Create3GenerateSaltError> for PyErr {
+    fn from(e: Create3GenerateSaltError) -> Self {
+        Create3Error::new_err(e.to_string())
+    }
+}
+
+/// calculates the create3 address for a deployer and a salt string.
+///
+/// arguments:
+/// - deployer: 20-byte create3 deployer address.
+/// - salt: salt bytes; their keccak256 digest is used.
+///
+/// returns: the 20-byte contract address.
+#[pyfunction]
+fn calc_addr(deployer: &[u8], salt: &[u8]) -> PyResult<[u8; 20]> {
+    crate::check_deployer(deployer)?;
+    Ok(crate::calc_addr(deployer, salt))
+}
+
+/// mines a salt whose address starts with a prefix, releasing the gil
+/// while the threads search.
+///
+/// arguments:
+/// - deployer: 20-byte create3 deployer address.
+/// - prefix: desired address prefix (without '0x').
+/// - threads: number of threads to spawn, or 0 for every core.
+///
+/// returns: (salt string, 32-byte keccak256 digest of salt).
+#[pyfunction]
+#[pyo3(signature = (deployer, prefix, threads = 0))]
+fn generate_salt(
+    py: Python<'_>,
+    deployer: &[u8],
+    prefix: &str,
+    threads: usize,
+) -> PyResult<(String, [u8; 32])> {
+    Ok(py.detach(|| {
+        crate::generate_salt_multithread(deployer, prefix, threads)
+    })?)
+}
+
+/// the `create3` python module.
+#[pymodule]
+fn create3(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(calc_addr, m)?)?;
+    m.add_function(wrap_pyfunction!(generate_salt, m)?)?;
+    m.add("Create3Error", m.py().get_type::<Create3Error>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::prelude::*;
+
+    use crate::python::create3;
+
+    #[test]
+    fn should_import_module_and_calc_addr() {
+        pyo3::append_to_inittab!(create3);
+        Python::initialize();
+        Python::attach(|py| {
+            let locals = pyo3::types::PyDict::new(py);
+            py.run(
+                c"
+import create3
+deployer = bytes.fromhex('De2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343B5')
+address = create3.calc_addr(deployer, b'nacl').hex()
+salt, salt_hash = create3.generate_salt(deployer, 'a', 1)
+mined = create3.calc_addr(deployer, salt.encode()).hex()
+try:
+    create3.generate_salt(deployer, 'xyz', 1)
+    error = None
+except create3.Create3Error as e:
+    error = str(e)
+",
+                None,
+                Some(&locals),
+            )
+            .unwrap();
+            let get = |key: &str| -> String {
+                locals.get_item(key).unwrap().unwrap().extract().unwrap()
+            };
+            assert_eq!(
+                get("address"),
+                "8b9A192B07bb8de5615545C620738c2713B97D4d".to_lowercase()
+            );
+            assert!(get("mined").starts_with('a'));
+            assert_eq!(get("error"), "prefix not hex encoded.");
+        });
+    }
+}
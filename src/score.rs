@@ -0,0 +1,506 @@
+use sha3::{Digest, Keccak256};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::{
+    calc_addr_with_bytes, check_deployer, errors::Create3GenerateSaltError,
+    generate_salt_prefix_multithread_counted, sanitize_hex_prefix,
+    search_parallel, to_nibbles, SaltResult, SearchControl,
+};
+
+/// counts the zero nibbles at the start of an address.
+///
+/// returns: 0 to 40; each leading zero byte saves gas in calldata.
+pub fn count_leading_zero_nibbles(addr: &[u8; 20]) -> u32 {
+    match addr.iter().position(|&b| b != 0) {
+        Some(i) => i as u32 * 2 + (addr[i] >> 4 == 0) as u32,
+        None => 40,
+    }
+}
+
+/// checks whether an address is the zero address, which burns anything
+/// sent to it.
+pub fn is_zero_address(addr: &[u8; 20]) -> bool {
+    addr.iter().all(|&b| b == 0)
+}
+
+/// tries a fixed number of salts and keeps the one whose address has the
+/// most leading zero nibbles, instead of stopping at a target prefix.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+/// - max_attempts: number of salts to try across all threads.
+///
+/// returns: the best salt seen, or a `NotFound` error if `max_attempts`
+/// is 0.
+pub fn mine_most_zeros(
+    deployer: &[u8],
+    thread_count: usize,
+    max_attempts: u64,
+) -> Result<SaltResult, Create3GenerateSaltError> {
+    check_deployer(deployer)?;
+    let attempts = AtomicU64::new(0);
+    let best: Mutex<Option<BestMatch>> = Mutex::new(None);
+    // one more than the zero count of the best candidate, so workers only
+    // take the lock for a candidate that may beat it.
+    let best_rank = AtomicUsize::new(0);
+    // nothing matches, so the search always runs out its budget.
+    let searched = search_parallel(
+        deployer,
+        "",
+        thread_count,
+        SearchControl {
+            stop: &AtomicBool::new(false),
+            max_attempts,
+            attempts: &attempts,
+            progress: None,
+        },
+        |salt, address| {
+            let zeros = count_leading_zero_nibbles(address) as usize;
+            if zeros < best_rank.load(Ordering::Relaxed) {
+                return false;
+            }
+            if let Ok(mut best) = best.lock() {
+                if best.as_ref().is_none_or(|b| zeros > b.0) {
+                    *best = Some((zeros, salt.to_owned(), *address));
+                    best_rank.store(zeros + 1, Ordering::Relaxed);
+                }
+            }
+            false
+        },
+    );
+    match searched {
+        Err(Create3GenerateSaltError::NotFound { .. }) => {}
+        Err(e) => return Err(e),
+        Ok(_) => unreachable!("no salt is ever accepted"),
+    }
+    let (_, salt, address) = best
+        .into_inner()
+        .map_err(|_| Create3GenerateSaltError::WorkerPanicked)?
+        .ok_or(Create3GenerateSaltError::NotFound { attempts: 0 })?;
+    Ok(SaltResult {
+        salt_hash: Keccak256::digest(&salt).into(),
+        salt,
+        address,
+        attempts: attempts.into_inner(),
+    })
+}
+
+/// tries a fixed number of salts and keeps the `k` whose addresses match
+/// the most leading characters of a prefix, for looking at near misses.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - target_prefix: desired address prefix (without '0x').
+/// - k: number of candidates to keep.
+/// - max_attempts: number of salts to try across all threads.
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: up to `k` distinct salts, sorted by matched prefix length,
+/// longest first.
+pub fn mine_top_k(
+    deployer: &[u8],
+    target_prefix: &str,
+    k: usize,
+    max_attempts: u64,
+    thread_count: usize,
+) -> Result<Vec<SaltResult>, Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(target_prefix)?);
+    check_deployer(deployer)?;
+    if k == 0 {
+        return Ok(Vec::new());
+    }
+    let attempts = AtomicU64::new(0);
+    // min-heap of the best candidates, so the worst one is evicted first.
+    let top: Mutex<BinaryHeap<Reverse<TopCandidate>>> =
+        Mutex::new(BinaryHeap::with_capacity(k + 1));
+    // matched length a candidate needs to get in, so most salts are
+    // rejected without taking the lock.
+    let threshold = AtomicUsize::new(0);
+    let searched = search_parallel(
+        deployer,
+        "",
+        thread_count,
+        SearchControl {
+            stop: &AtomicBool::new(false),
+            max_attempts,
+            attempts: &attempts,
+            progress: None,
+        },
+        |salt, address| {
+            let matched = matching_prefix_len(address, &prefix);
+            if matched < threshold.load(Ordering::Relaxed) {
+                return false;
+            }
+            let Ok(mut top) = top.lock() else {
+                return false;
+            };
+            if top.iter().any(|Reverse((_, s, _))| s == salt) {
+                return false;
+            }
+            top.push(Reverse((matched, salt.to_owned(), *address)));
+            if top.len() > k {
+                top.pop();
+            }
+            if top.len() == k {
+                let worst = top.peek().map_or(0, |Reverse(c)| c.0);
+                threshold.store(worst + 1, Ordering::Relaxed);
+            }
+            false
+        },
+    );
+    match searched {
+        Err(Create3GenerateSaltError::NotFound { .. }) => {}
+        Err(e) => return Err(e),
+        Ok(_) => unreachable!("no salt is ever accepted"),
+    }
+    let top = top
+        .into_inner()
+        .map_err(|_| Create3GenerateSaltError::WorkerPanicked)?
+        .into_sorted_vec();
+    let attempts = attempts.into_inner();
+    Ok(top
+        .into_iter()
+        .map(|Reverse((_, salt, address))| SaltResult {
+            salt_hash: Keccak256::digest(&salt).into(),
+            salt,
+            address,
+            attempts,
+        })
+        .collect())
+}
+
+/// matched prefix length, salt and address of a `mine_top_k` candidate.
+type TopCandidate = (usize, String, [u8; 20]);
+
+/// counts how many nibbles at the start of an address match a prefix.
+///
+/// arguments:
+/// - addr: 20-byte address to check.
+/// - prefix_nibbles: target leading nibbles, one value (0-15) per element.
+///
+/// returns: the length of the matching run, up to `prefix_nibbles.len()`.
+pub fn matching_prefix_len(addr: &[u8; 20], prefix_nibbles: &[u8]) -> usize {
+    prefix_nibbles
+        .iter()
+        .enumerate()
+        .take_while(|&(i, &nibble)| {
+            let byte = addr[i / 2];
+            let actual = if i % 2 == 0 { byte >> 4 } else { byte & 0x0f };
+            actual == nibble
+        })
+        .count()
+}
+
+/// mines an address that starts with the same hex character repeated,
+/// e.g. "0x77777...".
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - nibble: the hex character to repeat.
+/// - run_len: number of leading characters that must equal it (max 20).
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: the matching salt and the length of the run its address
+/// actually starts with, which can exceed `run_len`.
+pub fn mine_repeated_prefix(
+    deployer: &[u8],
+    nibble: char,
+    run_len: usize,
+    thread_count: usize,
+) -> Result<(SaltResult, usize), Create3GenerateSaltError> {
+    let Some(value) = nibble.to_digit(16) else {
+        return Err(Create3GenerateSaltError::PrefixNotHexEncoded);
+    };
+    let prefix = nibble.to_string().repeat(run_len);
+    let (salt, salt_hash, attempts) = generate_salt_prefix_multithread_counted(
+        deployer,
+        "",
+        &prefix,
+        thread_count,
+        Arc::new(AtomicU64::new(0)),
+    )?;
+    let address = calc_addr_with_bytes(deployer, &salt_hash);
+    let run = matching_prefix_len(&address, &[value as u8; 40]);
+    let result = SaltResult {
+        salt,
+        salt_hash,
+        address,
+        attempts,
+    };
+    Ok((result, run))
+}
+
+/// mines for a fixed time and keeps the salt whose address matches the
+/// most leading characters of a prefix, even if the whole prefix is
+/// never hit.
+///
+/// stops early once the full prefix matches. the search runs until at
+/// least one salt has been tried, so a result is returned even for a zero
+/// duration.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - target_prefix: desired address prefix (without '0x').
+/// - duration: how long to search.
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: the best salt seen and how many prefix characters its address
+/// matches.
+pub fn mine_best_effort(
+    deployer: &[u8],
+    target_prefix: &str,
+    duration: Duration,
+    thread_count: usize,
+) -> Result<(SaltResult, usize), Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(target_prefix)?);
+    check_deployer(deployer)?;
+    let stop = AtomicBool::new(false);
+    let attempts = AtomicU64::new(0);
+    let best: Mutex<Option<BestMatch>> = Mutex::new(None);
+    // one more than the matched length of the best candidate, so workers
+    // only take the lock for a candidate that may beat it.
+    let best_rank = AtomicUsize::new(0);
+    let searched = search_parallel(
+        deployer,
+        "",
+        thread_count,
+        SearchControl {
+            stop: &stop,
+            max_attempts: u64::MAX,
+            attempts: &attempts,
+            // past the deadline, stop as soon as there is a candidate.
+            progress: Some((duration, &mut |_| {
+                if best.lock().is_ok_and(|best| best.is_some()) {
+                    stop.store(true, Ordering::Relaxed);
+                }
+            })),
+        },
+        |salt, address| {
+            let matched = matching_prefix_len(address, &prefix);
+            if matched == prefix.len() {
+                return true;
+            }
+            if matched < best_rank.load(Ordering::Relaxed) {
+                return false;
+            }
+            if let Ok(mut best) = best.lock() {
+                if best.as_ref().is_none_or(|b| matched > b.0) {
+                    *best = Some((matched, salt.to_owned(), *address));
+                    best_rank.store(matched + 1, Ordering::Relaxed);
+                }
+            }
+            false
+        },
+    );
+    let (matched, salt, address) = match searched {
+        Ok((salt, salt_hash)) => (
+            prefix.len(),
+            salt,
+            calc_addr_with_bytes(deployer, &salt_hash),
+        ),
+        // the deadline only stops the search once a salt has been tried.
+        Err(Create3GenerateSaltError::Cancelled) => best
+            .into_inner()
+            .map_err(|_| Create3GenerateSaltError::WorkerPanicked)?
+            .unwrap(),
+        Err(e) => return Err(e),
+    };
+    Ok((
+        SaltResult {
+            salt_hash: Keccak256::digest(&salt).into(),
+            salt,
+            address,
+            attempts: attempts.into_inner(),
+        },
+        matched,
+    ))
+}
+
+/// rank, salt and address of the best candidate, ranked by matched
+/// prefix length or by leading zeros.
+type BestMatch = (usize, String, [u8; 20]);
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        calc_addr,
+        errors::Create3GenerateSaltError,
+        score::{
+            count_leading_zero_nibbles, is_zero_address, matching_prefix_len,
+            mine_best_effort, mine_most_zeros, mine_repeated_prefix,
+            mine_top_k,
+        },
+        to_nibbles, PANICKING_DEPLOYER,
+    };
+
+    #[test]
+    fn should_detect_zero_address() {
+        assert!(is_zero_address(&[0; 20]));
+        let mut addr = [0u8; 20];
+        addr[19] = 1;
+        assert!(!is_zero_address(&addr));
+        addr[19] = 0;
+        addr[0] = 0x10;
+        assert!(!is_zero_address(&addr));
+    }
+
+    #[test]
+    fn should_mine_repeated_prefix() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let (result, run) = mine_repeated_prefix(&deployer, '0', 3, 2).unwrap();
+        assert!(run >= 3);
+        assert_eq!(count_leading_zero_nibbles(&result.address) as usize, run);
+        assert_eq!(
+            calc_addr(&deployer, result.salt.as_bytes()),
+            result.address
+        );
+        assert!(result.attempts > 0);
+
+        let (result, run) = mine_repeated_prefix(&deployer, 'B', 2, 2).unwrap();
+        let address = hex::encode(result.address);
+        assert!(address.starts_with(&"b".repeat(run)));
+        assert!(!address[run..].starts_with('b'));
+
+        assert_eq!(
+            mine_repeated_prefix(&deployer, 'g', 3, 2),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+        assert_eq!(
+            mine_repeated_prefix(&deployer, '7', 21, 2),
+            Err(Create3GenerateSaltError::PrefixTooLong)
+        );
+    }
+
+    #[test]
+    fn should_count_leading_zero_nibbles() {
+        let mut addr = [0u8; 20];
+        assert_eq!(count_leading_zero_nibbles(&addr), 40);
+        addr[0] = 0x0f;
+        assert_eq!(count_leading_zero_nibbles(&addr), 1);
+        addr[0] = 0xf0;
+        assert_eq!(count_leading_zero_nibbles(&addr), 0);
+        let mut addr = [0u8; 20];
+        addr[3] = 0x01;
+        assert_eq!(count_leading_zero_nibbles(&addr), 7);
+        addr[19] = 0xff;
+        assert_eq!(count_leading_zero_nibbles(&addr), 7);
+    }
+
+    #[test]
+    fn should_mine_most_zeros_within_budget() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let result = mine_most_zeros(&deployer, 2, 2_000).unwrap();
+        assert_eq!(result.attempts, 2_000);
+        assert_eq!(
+            calc_addr(&deployer, result.salt.as_bytes()),
+            result.address
+        );
+        // 2000 tries all missing a single leading zero is ~1e-56 likely.
+        assert!(count_leading_zero_nibbles(&result.address) >= 1);
+        assert_eq!(
+            mine_most_zeros(&deployer, 2, 0),
+            Err(Create3GenerateSaltError::NotFound { attempts: 0 })
+        );
+    }
+
+    #[test]
+    fn mine_top_k_should_return_candidates_in_descending_match_order() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let prefix = to_nibbles("abc");
+        let top = mine_top_k(&deployer, "abc", 3, 2_000, 2).unwrap();
+        assert_eq!(top.len(), 3);
+        let matched: Vec<usize> = top
+            .iter()
+            .map(|r| matching_prefix_len(&r.address, &prefix))
+            .collect();
+        assert!(matched.windows(2).all(|w| w[0] >= w[1]));
+        // 2000 tries almost surely hit the first character at least 3 times.
+        assert!(matched[2] >= 1);
+        for result in &top {
+            assert_eq!(
+                calc_addr(&deployer, result.salt.as_bytes()),
+                result.address
+            );
+            assert_eq!(result.attempts, 2_000);
+        }
+        assert!(top[0].salt != top[1].salt && top[1].salt != top[2].salt);
+        assert!(top[0].salt != top[2].salt);
+
+        assert_eq!(mine_top_k(&deployer, "abc", 5, 2, 2).unwrap().len(), 2);
+        assert!(mine_top_k(&deployer, "abc", 0, 100, 2).unwrap().is_empty());
+        assert_eq!(
+            mine_top_k(&deployer, "xyz", 3, 100, 2),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+
+    #[test]
+    fn budgeted_mining_should_report_worker_panics() {
+        assert_eq!(
+            mine_most_zeros(&PANICKING_DEPLOYER, 2, 2_000),
+            Err(Create3GenerateSaltError::WorkerPanicked)
+        );
+        assert_eq!(
+            mine_top_k(&PANICKING_DEPLOYER, "abc", 3, 2_000, 2),
+            Err(Create3GenerateSaltError::WorkerPanicked)
+        );
+    }
+
+    #[test]
+    fn should_count_matching_prefix_len() {
+        let addr = [0xab; 20];
+        assert_eq!(matching_prefix_len(&addr, &[]), 0);
+        assert_eq!(matching_prefix_len(&addr, &[0xa, 0xb, 0xa, 0xc]), 3);
+        assert_eq!(matching_prefix_len(&addr, &[0xb]), 0);
+        assert_eq!(matching_prefix_len(&addr, &[0xa, 0xb, 0xa]), 3);
+    }
+
+    #[test]
+    fn mine_best_effort_should_return_a_valid_salt_in_time() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for duration in [Duration::ZERO, Duration::from_millis(20)] {
+            let (result, matched) =
+                mine_best_effort(&deployer, "a", duration, 2).unwrap();
+            assert!(matched <= 1);
+            assert!(result.attempts >= 1);
+            assert_eq!(
+                calc_addr(&deployer, result.salt.as_bytes()),
+                result.address
+            );
+            assert_eq!(
+                hex::encode(result.address).starts_with('a'),
+                matched == 1
+            );
+        }
+        // far too long a prefix to hit, so the best partial match is kept.
+        let (result, matched) = mine_best_effort(
+            &deployer,
+            "0123456789abcdef",
+            Duration::from_millis(50),
+            2,
+        )
+        .unwrap();
+        assert!(matched < 16);
+        assert!(hex::encode(result.address)
+            .starts_with(&"0123456789abcdef"[..matched]));
+        assert_eq!(
+            mine_best_effort(&deployer, "xyz", Duration::ZERO, 2),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use alloc::{format, string::String};
+
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+/// serializes a byte array as a 0x-prefixed hex string.
+pub(crate) fn serialize<S: Serializer, const N: usize>(
+    bytes: &[u8; N],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+}
+
+/// deserializes a byte array from a hex string, with or without '0x'.
+pub(crate) fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> Result<[u8; N], D::Error> {
+    let s = String::deserialize(deserializer)?;
+    let digits = s.strip_prefix("0x").unwrap_or(&s);
+    let mut bytes = [0u8; N];
+    hex::decode_to_slice(digits, &mut bytes).map_err(D::Error::custom)?;
+    Ok(bytes)
+}
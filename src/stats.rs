@@ -0,0 +1,323 @@
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::time::{Duration, Instant};
+
+use crate::{
+    calc_addr, calc_addr_with_bytes, check_deployer,
+    errors::Create3GenerateSaltError, matches_prefix, random_salt,
+    resolve_thread_count, sanitize_hex_prefix, search_parallel, search_rng,
+    to_nibbles, SaltResult, SearchControl,
+};
+
+// time between two progress reports.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// progress of a running search.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MiningStats {
+    /// salts tried so far, across all threads.
+    pub attempts: u64,
+    /// time since the search started.
+    pub elapsed: Duration,
+    /// average attempts per second so far.
+    pub hashes_per_sec: f64,
+}
+
+/// summary of a finished search.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MiningMetrics {
+    /// salts tried, summed across all threads.
+    pub attempts: u64,
+    /// time the search took.
+    pub elapsed: Duration,
+    /// average attempts per second over the whole search.
+    pub hashes_per_sec: f64,
+    /// number of threads that mined.
+    pub threads: usize,
+}
+
+/// outcome of a fixed-length search that counts every match.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchStats {
+    /// salts tried.
+    pub attempts: u64,
+    /// salts whose address matched the prefix.
+    pub matches: u64,
+    /// observed matches per million attempts, to compare against
+    /// `1_000_000 / expected_attempts(prefix)`.
+    pub matches_per_million: f64,
+}
+
+/// tries a fixed number of random salts and counts how many match a prefix.
+///
+/// a large gap between the observed rate and the one implied by
+/// `expected_attempts` points at a bug in the matcher.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - attempts: number of random salts to try.
+///
+/// returns: the number of matches and the observed match rate.
+pub fn search_match_rate(
+    deployer: &[u8],
+    prefix: &str,
+    attempts: u64,
+) -> Result<SearchStats, Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(deployer)?;
+    let mut rng = search_rng();
+    let matches = (0..attempts)
+        .filter(|_| {
+            let salt = random_salt(&mut rng, 10);
+            matches_prefix(&calc_addr(deployer, salt.as_bytes()), &prefix)
+        })
+        .count() as u64;
+    let matches_per_million = if attempts == 0 {
+        0.0
+    } else {
+        matches as f64 * 1_000_000.0 / attempts as f64
+    };
+    Ok(SearchStats {
+        attempts,
+        matches,
+        matches_per_million,
+    })
+}
+
+/// counts how often each value appears as the first nibble of the address
+/// across random salts, for checking that addresses are uniform.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - sample: number of random salts to draw.
+///
+/// returns: counts indexed by first nibble value (0-15).
+pub fn first_nibble_histogram(deployer: &[u8], sample: u64) -> [u64; 16] {
+    let mut histogram = [0u64; 16];
+    let mut rng = search_rng();
+    for _ in 0..sample {
+        let address = calc_addr(deployer, random_salt(&mut rng, 10).as_bytes());
+        histogram[(address[0] >> 4) as usize] += 1;
+    }
+    histogram
+}
+
+/// generates a salt for a given prefix using multiple threads, reporting
+/// progress about once a second.
+///
+/// `on_progress` is called from the calling thread and never with stats
+/// taken after a match has been found.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+/// - on_progress: callback receiving the current stats.
+///
+/// returns: the matching salt and its address.
+pub fn generate_salt_with_progress(
+    deployer: &[u8],
+    prefix: &str,
+    thread_count: usize,
+    on_progress: impl Fn(MiningStats),
+) -> Result<SaltResult, Create3GenerateSaltError> {
+    generate_salt_with_progress_every(
+        deployer,
+        prefix,
+        thread_count,
+        PROGRESS_INTERVAL,
+        on_progress,
+    )
+}
+
+/// generates a salt for a given prefix using multiple threads and reports
+/// how the search went.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: the matching salt and the metrics of the search.
+pub fn generate_salt_with_metrics(
+    deployer: &[u8],
+    prefix: &str,
+    thread_count: usize,
+) -> Result<(SaltResult, MiningMetrics), Create3GenerateSaltError> {
+    let threads = resolve_thread_count(thread_count);
+    let start = Instant::now();
+    let result =
+        generate_salt_with_progress(deployer, prefix, threads, |_| {})?;
+    let elapsed = start.elapsed();
+    // an instant match can take less than a nanosecond to time.
+    let hashes_per_sec =
+        result.attempts as f64 / elapsed.as_secs_f64().max(1e-9);
+    let metrics = MiningMetrics {
+        attempts: result.attempts,
+        elapsed,
+        hashes_per_sec,
+        threads,
+    };
+    Ok((result, metrics))
+}
+
+/// `generate_salt_with_progress` with a configurable report interval.
+fn generate_salt_with_progress_every(
+    deployer: &[u8],
+    prefix: &str,
+    thread_count: usize,
+    interval: Duration,
+    on_progress: impl Fn(MiningStats),
+) -> Result<SaltResult, Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(deployer)?;
+    let attempts = AtomicU64::new(0);
+    let (salt, salt_hash) = search_parallel(
+        deployer,
+        "",
+        thread_count,
+        SearchControl {
+            stop: &AtomicBool::new(false),
+            max_attempts: u64::MAX,
+            attempts: &attempts,
+            progress: Some((interval, &mut |stats| on_progress(stats))),
+        },
+        |_, address| matches_prefix(address, &prefix),
+    )?;
+    Ok(SaltResult {
+        address: calc_addr_with_bytes(deployer, &salt_hash),
+        salt,
+        salt_hash,
+        attempts: attempts.into_inner(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use crate::{
+        calc_addr,
+        difficulty::expected_attempts,
+        errors::Create3GenerateSaltError,
+        resolve_thread_count,
+        stats::{
+            first_nibble_histogram, generate_salt_with_metrics,
+            generate_salt_with_progress_every, search_match_rate,
+        },
+        PANICKING_DEPLOYER,
+    };
+
+    #[test]
+    fn first_nibble_histogram_should_be_uniform() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let sample = 16_000;
+        let histogram = first_nibble_histogram(&deployer, sample);
+        assert_eq!(histogram.iter().sum::<u64>(), sample);
+        // chi-square with 15 degrees of freedom; 50 is far beyond p = 0.001.
+        let expected = sample as f64 / 16.0;
+        let chi_square: f64 = histogram
+            .iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+        assert!(chi_square < 50.0, "chi-square {}", chi_square);
+    }
+
+    #[test]
+    fn match_rate_should_agree_with_expected_attempts() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let stats = search_match_rate(&deployer, "a", 8_000).unwrap();
+        assert_eq!(stats.attempts, 8_000);
+        let expected = 1_000_000.0 / expected_attempts("a").unwrap();
+        // ~500 expected matches, so 25% is more than five sigma.
+        assert!(
+            (stats.matches_per_million - expected).abs() < expected * 0.25,
+            "observed {} per million",
+            stats.matches_per_million
+        );
+        let stats = search_match_rate(&deployer, "", 100).unwrap();
+        assert_eq!(stats.matches, 100);
+        assert_eq!(stats.matches_per_million, 1_000_000.0);
+        assert_eq!(
+            search_match_rate(&deployer, "xyz", 100),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+
+    #[test]
+    fn should_report_progress_while_mining() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let reports = Mutex::new(Vec::new());
+        // progress is polled every few milliseconds and a lucky search can
+        // match before the first poll, so mine until one has reported.
+        for _ in 0..50 {
+            let result = generate_salt_with_progress_every(
+                &deployer,
+                "abc",
+                2,
+                Duration::from_millis(1),
+                |stats| reports.lock().unwrap().push(stats),
+            )
+            .unwrap();
+            assert_eq!(
+                calc_addr(&deployer, result.salt.as_bytes()),
+                result.address
+            );
+            assert!(hex::encode(result.address).starts_with("abc"));
+            if !reports.lock().unwrap().is_empty() {
+                break;
+            }
+        }
+        let reports = reports.into_inner().unwrap();
+        assert!(!reports.is_empty());
+        for stats in reports.iter() {
+            assert!(stats.hashes_per_sec.is_finite());
+            assert!(stats.elapsed > Duration::ZERO);
+        }
+    }
+
+    #[test]
+    fn should_report_metrics_after_mining() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        for prefix in ["", "ab"] {
+            let (result, metrics) =
+                generate_salt_with_metrics(&deployer, prefix, 2).unwrap();
+            assert!(hex::encode(result.address).starts_with(prefix));
+            assert_eq!(metrics.attempts, result.attempts);
+            assert!(metrics.attempts >= 1);
+            assert!(metrics.hashes_per_sec.is_finite());
+            assert!(metrics.hashes_per_sec > 0.0);
+            assert_eq!(metrics.threads, resolve_thread_count(2));
+        }
+        // 0 mines, and reports, every core.
+        let (_, metrics) =
+            generate_salt_with_metrics(&deployer, "a", 0).unwrap();
+        assert_eq!(
+            metrics.threads,
+            std::thread::available_parallelism().map_or(1, |n| n.get())
+        );
+    }
+
+    #[test]
+    fn progress_should_error_when_a_worker_panics() {
+        assert_eq!(
+            generate_salt_with_progress_every(
+                &PANICKING_DEPLOYER,
+                "abcdef",
+                3,
+                Duration::from_millis(1),
+                |_| {},
+            ),
+            Err(Create3GenerateSaltError::WorkerPanicked)
+        );
+    }
+}
@@ -0,0 +1,486 @@
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    calc_addr, check_deployer, errors::Create3GenerateSaltError, generate_salt,
+    matches_prefix, resolve_thread_count, sanitize_hex_prefix, search_parallel,
+    to_nibbles, SaltResult, SearchControl,
+};
+
+/// mines salts for a given prefix on multiple threads, sending every match
+/// down a channel.
+///
+/// blocks until the receiving end of the channel is dropped, at which point
+/// the workers stop.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - thread_count: number of threads to spawn (at least 1).
+/// - tx: channel each matching salt is sent to.
+pub fn generate_salts_streaming(
+    deployer: &[u8],
+    prefix: &str,
+    thread_count: usize,
+    tx: Sender<SaltResult>,
+) -> Result<(), Create3GenerateSaltError> {
+    // no workers would mean returning at once, as if the receiver had
+    // been dropped.
+    if thread_count == 0 {
+        return Err(Create3GenerateSaltError::InvalidThreadCount);
+    }
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(deployer)?;
+    stream_matches(deployer, &prefix, thread_count, tx, |tx, result| {
+        tx.send(result).is_ok()
+    })
+}
+
+/// mines salts for a given prefix on multiple threads in the background,
+/// handing matches over through a bounded channel.
+///
+/// once `buffer` matches are waiting, workers block until the consumer
+/// takes one, so a slow consumer throttles the search instead of letting
+/// matches pile up. the workers stop when the receiver is dropped.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+/// - buffer: number of matches that may wait in the channel.
+///
+/// returns: the receiving end of the channel.
+pub fn generate_salts_bounded_channel(
+    deployer: &[u8],
+    prefix: &str,
+    thread_count: usize,
+    buffer: usize,
+) -> Result<Receiver<SaltResult>, Create3GenerateSaltError> {
+    spawn_bounded_channel(deployer, prefix, thread_count, buffer)
+        .map(|(rx, _)| rx)
+}
+
+// receiver of a bounded channel, and the thread driving its workers.
+type BoundedChannel = (
+    Receiver<SaltResult>,
+    thread::JoinHandle<Result<(), Create3GenerateSaltError>>,
+);
+
+/// starts the workers behind `generate_salts_bounded_channel`.
+///
+/// returns: the receiver, and a handle which finishes once every worker
+/// has stopped.
+fn spawn_bounded_channel(
+    deployer: &[u8],
+    prefix: &str,
+    thread_count: usize,
+    buffer: usize,
+) -> Result<BoundedChannel, Create3GenerateSaltError> {
+    let prefix = to_nibbles(&sanitize_hex_prefix(prefix)?);
+    check_deployer(deployer)?;
+    let (tx, rx) = mpsc::sync_channel(buffer);
+    let deployer = deployer.to_owned();
+    let miner = thread::spawn(move || {
+        stream_matches(&deployer, &prefix, thread_count, tx, |tx, result| {
+            tx.send(result).is_ok()
+        })
+    });
+    Ok((rx, miner))
+}
+
+/// runs the shared search with a matcher which hands every match to
+/// `send` until it reports that the receiver is gone.
+///
+/// blocks until every worker has stopped.
+fn stream_matches<T: Sync>(
+    deployer: &[u8],
+    prefix: &[u8],
+    thread_count: usize,
+    tx: T,
+    send: fn(&T, SaltResult) -> bool,
+) -> Result<(), Create3GenerateSaltError> {
+    thread_local! {
+        // attempts since this worker's previous match.
+        static SINCE_MATCH: Cell<u64> = const { Cell::new(0) };
+    }
+    let stop = AtomicBool::new(false);
+    let searched = search_parallel(
+        deployer,
+        "",
+        thread_count,
+        SearchControl {
+            stop: &stop,
+            max_attempts: u64::MAX,
+            attempts: &AtomicU64::new(0),
+            progress: None,
+        },
+        |salt, address| {
+            let attempts = SINCE_MATCH.get() + 1;
+            if !matches_prefix(address, prefix) {
+                SINCE_MATCH.set(attempts);
+                return false;
+            }
+            SINCE_MATCH.set(0);
+            let result = SaltResult {
+                salt: salt.to_owned(),
+                salt_hash: Keccak256::digest(salt).into(),
+                address: *address,
+                attempts,
+            };
+            if !send(&tx, result) {
+                // receiver is gone; nobody wants more results.
+                stop.store(true, Ordering::Relaxed);
+            }
+            // keep searching; only the receiver ends the stream.
+            false
+        },
+    );
+    match searched {
+        Ok(_) | Err(Create3GenerateSaltError::Cancelled) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// mines a number of distinct salts for a given prefix on multiple threads.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - count: number of salts to generate.
+/// - thread_count: number of threads to spawn (capped at the core count),
+///   or 0 to use every available core.
+///
+/// returns: `count` results with distinct addresses, in the order found.
+pub fn generate_salts(
+    deployer: &[u8],
+    prefix: &str,
+    count: usize,
+    thread_count: usize,
+) -> Result<Vec<SaltResult>, Create3GenerateSaltError> {
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|s| {
+        let miner = s.spawn(|| {
+            generate_salts_streaming(
+                deployer,
+                prefix,
+                resolve_thread_count(thread_count),
+                tx,
+            )
+        });
+        let mut seen = HashSet::new();
+        let results: Vec<SaltResult> = rx
+            .iter()
+            .filter(|result| seen.insert(result.address))
+            .take(count)
+            .collect();
+        // dropping the receiver stops the workers.
+        drop(rx);
+        miner
+            .join()
+            .map_err(|_| Create3GenerateSaltError::WorkerPanicked)??;
+        Ok(results)
+    })
+}
+
+/// generates salts for a given prefix, writing each one to `writer` as a
+/// line of json as soon as it is found.
+///
+/// each line has the form
+/// `{"address":"0x..","salt":"..","salt_hash":"0x.."}` and is flushed
+/// before the next search starts, so consumers can read results while the
+/// batch is still running.
+///
+/// arguments:
+/// - deployer: create3 deployer address (bytes).
+/// - prefix: desired address prefix (without '0x').
+/// - count: number of salts to generate.
+/// - writer: destination for the json lines.
+pub fn stream_ndjson<W: Write>(
+    deployer: &[u8],
+    prefix: &str,
+    count: usize,
+    mut writer: W,
+) -> io::Result<()> {
+    for _ in 0..count {
+        let (salt, salt_hash) = generate_salt(deployer, prefix)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        write_ndjson_line(
+            &mut writer,
+            &calc_addr(deployer, salt.as_bytes()),
+            &salt,
+            &salt_hash,
+        )?;
+    }
+    Ok(())
+}
+
+/// writes one result as a flushed line of json, in the format used by
+/// `stream_ndjson`.
+///
+/// arguments:
+/// - writer: destination for the json line.
+/// - address: 20-byte contract address.
+/// - salt: salt string, escaped as a json string.
+/// - salt_hash: 32-byte keccak256 digest of the salt.
+pub fn write_ndjson_line<W: Write>(
+    writer: &mut W,
+    address: &[u8; 20],
+    salt: &str,
+    salt_hash: &[u8; 32],
+) -> io::Result<()> {
+    writeln!(
+        writer,
+        "{{\"address\":\"0x{}\",\"salt\":\"{}\",\"salt_hash\":\"0x{}\"}}",
+        hex::encode(address),
+        json_escape(salt),
+        hex::encode(salt_hash)
+    )?;
+    writer.flush()
+}
+
+/// reads the address back from a line written by `write_ndjson_line`.
+///
+/// returns: the 20-byte address, or None if the line isn't such a result.
+pub fn parse_ndjson_address(line: &str) -> Option<[u8; 20]> {
+    let digits = line.strip_prefix("{\"address\":\"0x")?.get(..40)?;
+    let mut address = [0u8; 20];
+    hex::decode_to_slice(digits, &mut address).ok()?;
+    Some(address)
+}
+
+/// header line matching the columns written by `write_csv_line`.
+pub const CSV_HEADER: &str = "address,salt,salt_hash";
+
+/// writes one result as a flushed csv row of address, salt and salt hash.
+///
+/// arguments:
+/// - writer: destination for the csv row.
+/// - address: 20-byte contract address.
+/// - salt: salt string, quoted if it contains a comma, quote or newline.
+/// - salt_hash: 32-byte keccak256 digest of the salt.
+pub fn write_csv_line<W: Write>(
+    writer: &mut W,
+    address: &[u8; 20],
+    salt: &str,
+    salt_hash: &[u8; 32],
+) -> io::Result<()> {
+    let salt = if salt.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", salt.replace('"', "\"\""))
+    } else {
+        salt.to_owned()
+    };
+    writeln!(
+        writer,
+        "0x{},{},0x{}",
+        hex::encode(address),
+        salt,
+        hex::encode(salt_hash)
+    )?;
+    writer.flush()
+}
+
+/// escapes a string for use inside a json string literal.
+///
+/// mined salts are alphanumeric, but salts typed in by hand may contain
+/// quotes, backslashes or control characters.
+fn json_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                escaped.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::io;
+    use std::sync::mpsc;
+    use std::thread;
+
+    use crate::{
+        calc_addr,
+        errors::Create3GenerateSaltError,
+        stream::{
+            generate_salts, generate_salts_bounded_channel,
+            generate_salts_streaming, spawn_bounded_channel, stream_ndjson,
+            write_csv_line, write_ndjson_line,
+        },
+        PANICKING_DEPLOYER,
+    };
+
+    #[test]
+    fn should_stream_matching_salts() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let (tx, rx) = mpsc::channel();
+        let miner = {
+            let deployer = deployer.clone();
+            thread::spawn(move || {
+                generate_salts_streaming(&deployer, "ab", 4, tx)
+            })
+        };
+        let results: Vec<_> = rx.iter().take(3).collect();
+        drop(rx);
+        assert_eq!(miner.join().unwrap(), Ok(()));
+        assert_eq!(results.len(), 3);
+        for result in results.iter() {
+            assert_eq!(
+                calc_addr(&deployer, result.salt.as_bytes()),
+                result.address
+            );
+            assert!(hex::encode(result.address).starts_with("ab"));
+        }
+    }
+
+    #[test]
+    fn bounded_channel_should_stop_workers_when_receiver_drops() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let (rx, miner) = spawn_bounded_channel(&deployer, "a", 3, 1).unwrap();
+        let results: Vec<_> = rx.iter().take(2).collect();
+        drop(rx);
+        // only returns once every worker, including any blocked on a full
+        // buffer, has stopped.
+        assert_eq!(miner.join().unwrap(), Ok(()));
+        assert_eq!(results.len(), 2);
+        for result in results.iter() {
+            assert_eq!(
+                calc_addr(&deployer, result.salt.as_bytes()),
+                result.address
+            );
+            assert!(hex::encode(result.address).starts_with('a'));
+        }
+
+        let rx = generate_salts_bounded_channel(&deployer, "b", 2, 4).unwrap();
+        assert!(hex::encode(rx.recv().unwrap().address).starts_with('b'));
+        assert_eq!(
+            generate_salts_bounded_channel(&deployer, "xyz", 2, 4).err(),
+            Some(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+
+    #[test]
+    fn streaming_should_reject_zero_threads() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let (tx, rx) = mpsc::channel();
+        assert_eq!(
+            generate_salts_streaming(&deployer, "0", 0, tx),
+            Err(Create3GenerateSaltError::InvalidThreadCount)
+        );
+        assert!(rx.recv().is_err());
+    }
+
+    #[test]
+    fn should_generate_distinct_salts() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let results = generate_salts(&deployer, "a", 3, 2).unwrap();
+        assert_eq!(results.len(), 3);
+        let addresses: HashSet<[u8; 20]> =
+            results.iter().map(|result| result.address).collect();
+        assert_eq!(addresses.len(), 3);
+        for result in results.iter() {
+            assert_eq!(
+                calc_addr(&deployer, result.salt.as_bytes()),
+                result.address
+            );
+            assert!(hex::encode(result.address).starts_with('a'));
+        }
+        assert_eq!(
+            generate_salts(&deployer, "xyz", 3, 2),
+            Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+        );
+    }
+
+    #[test]
+    fn generate_salts_should_error_when_workers_panic() {
+        assert_eq!(
+            generate_salts(&PANICKING_DEPLOYER, "a", 3, 2),
+            Err(Create3GenerateSaltError::WorkerPanicked)
+        );
+    }
+
+    #[test]
+    fn should_write_one_json_line_per_salt() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let mut out = Vec::new();
+        stream_ndjson(&deployer, "ab", 3, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 3);
+        for line in lines {
+            let fields: Vec<&str> = line
+                .trim_start_matches('{')
+                .trim_end_matches('}')
+                .split(',')
+                .map(|field| field.split(':').nth(1).unwrap().trim_matches('"'))
+                .collect();
+            let [address, salt, salt_hash] = fields[..] else {
+                panic!("unexpected line: {}", line);
+            };
+            assert!(line.starts_with("{\"address\":"));
+            assert_eq!(
+                address,
+                format!(
+                    "0x{}",
+                    hex::encode(calc_addr(&deployer, salt.as_bytes()))
+                )
+            );
+            assert!(address.starts_with("0xab"));
+            assert_eq!(salt_hash.len(), 66);
+        }
+    }
+
+    #[test]
+    fn stream_ndjson_should_error_on_invalid_prefix() {
+        let deployer: Vec<u8> =
+            hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
+        let err = stream_ndjson(&deployer, "xyz", 1, io::sink()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn write_ndjson_line_should_escape_salt() {
+        let mut out = Vec::new();
+        write_ndjson_line(&mut out, &[0; 20], "a\"b\\c\n\u{1}", &[0; 32])
+            .unwrap();
+        let line: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(line["salt"], "a\"b\\c\n\u{1}");
+    }
+
+    #[test]
+    fn write_csv_line_should_quote_salt_when_needed() {
+        let mut out = Vec::new();
+        write_csv_line(&mut out, &[0xab; 20], "abc", &[0; 32]).unwrap();
+        write_csv_line(&mut out, &[0xab; 20], "a,\"b\"", &[0; 32]).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        let address = format!("0x{}", "ab".repeat(20));
+        let salt_hash = format!("0x{}", "00".repeat(32));
+        assert_eq!(lines[0], format!("{},abc,{}", address, salt_hash));
+        assert_eq!(
+            lines[1],
+            format!("{},\"a,\"\"b\"\"\",{}", address, salt_hash)
+        );
+    }
+}
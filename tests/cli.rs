@@ -0,0 +1,322 @@
+//! runs the create3 binary with command-line arguments.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+fn run(args: &[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_create3"))
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+fn run_interactive(dir: &Path, args: &[&str], stdin: &str) -> (bool, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_create3"))
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+fn field<'a>(stdout: &'a str, key: &str) -> &'a str {
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(": "))
+        .unwrap()
+}
+
+#[test]
+fn calc_should_print_checksummed_address() {
+    let (ok, stdout) = run(&[
+        "calc",
+        "--deployer",
+        "0xDe2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343B5",
+        "--salt",
+        "nacl",
+    ]);
+    assert!(ok);
+    assert_eq!(
+        field(&stdout, "address"),
+        "0x8b9A192B07bb8de5615545C620738c2713B97D4d"
+    );
+    assert_eq!(field(&stdout, "salt"), "nacl");
+}
+
+#[test]
+fn mine_should_warn_when_max_attempts_is_unlikely_to_succeed() {
+    let output = Command::new(env!("CARGO_BIN_EXE_create3"))
+        .args([
+            "mine",
+            "--deployer",
+            "De2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343B5",
+            "--prefix",
+            "ffffffffff",
+            "--max-attempts",
+            "1000",
+            "--threads",
+            "1",
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("warning: 1000 attempts give only a 0.00% chance"));
+    assert!(stderr.contains("no match found"));
+
+    // a budget that almost surely suffices gets no warning.
+    let output = Command::new(env!("CARGO_BIN_EXE_create3"))
+        .args([
+            "mine",
+            "--deployer",
+            "De2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343B5",
+            "--prefix",
+            "a",
+            "--max-attempts",
+            "10000",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(!String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("warning"));
+}
+
+#[test]
+fn calc_should_accept_raw_salt_hash() {
+    let salt_hash =
+        "0x3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb";
+    let (ok, stdout) = run(&[
+        "calc",
+        "--deployer",
+        "d8b934580fcE35a11B58C6D73aDeE468a2833fa8",
+        "--salt-hash",
+        salt_hash,
+    ]);
+    assert!(ok);
+    assert_eq!(
+        field(&stdout, "address"),
+        "0x442188F25da4ac213D55aE81F1BFB421a4eb4562"
+    );
+    assert_eq!(field(&stdout, "salt hash"), salt_hash);
+
+    let calc = [
+        "calc",
+        "--deployer",
+        "d8b934580fcE35a11B58C6D73aDeE468a2833fa8",
+    ];
+    // '0x' is optional.
+    assert_eq!(
+        run(&[&calc[..], &["--salt-hash", &salt_hash[2..]]].concat()),
+        (ok, stdout)
+    );
+
+    // 31 bytes, non-hex and both salt forms at once are rejected.
+    for args in [
+        &["--salt-hash", &salt_hash[..64]][..],
+        &["--salt-hash", &salt_hash.replace('3', "z")],
+        &["--salt-hash", salt_hash, "--salt", "nacl"],
+    ] {
+        let (ok, _) = run(&[&calc[..], args].concat());
+        assert!(!ok);
+    }
+}
+
+#[test]
+fn mine_should_print_matching_salt() {
+    let deployer = "De2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343B5";
+    let (ok, stdout) = run(&[
+        "mine",
+        "--deployer",
+        deployer,
+        "--prefix",
+        "a",
+        "--suffix",
+        "b",
+        "--salt-prefix",
+        "v2_",
+        "--threads",
+        "2",
+    ]);
+    assert!(ok);
+    let address = field(&stdout, "address").to_lowercase();
+    assert!(address.starts_with("0xa"));
+    assert!(address.ends_with('b'));
+    let salt = field(&stdout, "salt");
+    assert!(salt.starts_with("v2_"));
+    let (ok, calc) = run(&["calc", "--deployer", deployer, "--salt", salt]);
+    assert!(ok);
+    assert_eq!(field(&calc, "address").to_lowercase(), address);
+}
+
+#[test]
+fn bench_should_print_positive_hash_rates() {
+    let (ok, stdout) = run(&["bench", "--seconds", "0.2", "--format", "json"]);
+    assert!(ok);
+    let threads: Vec<u64> = stdout
+        .lines()
+        .map(|line| {
+            let line: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert!(line["hashes_per_sec"].as_f64().unwrap() > 0.0);
+            assert!(line["attempts"].as_u64().unwrap() > 0);
+            line["threads"].as_u64().unwrap()
+        })
+        .collect();
+    assert_eq!(threads[0], 1);
+    assert!(threads.windows(2).all(|w| w[0] < w[1]));
+
+    let (ok, stdout) = run(&["bench", "--seconds", "0.2"]);
+    assert!(ok);
+    assert!(stdout.contains("  1 threads: "));
+    assert!(stdout.trim_end().ends_with("hashes/s"));
+}
+
+#[test]
+fn strict_checksum_should_reject_corrupted_deployer() {
+    let calc = |deployer: &str, strict: bool| {
+        let mut args = vec!["calc", "--deployer", deployer, "--salt", "nacl"];
+        if strict {
+            args.push("--strict-checksum");
+        }
+        run(&args).0
+    };
+    let checksummed = "0xDe2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343B5";
+    let corrupted = "0xDe2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343b5";
+    assert!(calc(checksummed, true));
+    assert!(calc(&checksummed.to_lowercase(), true));
+    assert!(calc(corrupted, false));
+    assert!(!calc(corrupted, true));
+}
+
+#[test]
+fn mine_should_fail_on_invalid_input() {
+    assert!(!run(&["mine", "--deployer", "0x12"]).0);
+    assert!(
+        !run(&[
+            "mine",
+            "--deployer",
+            "De2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343B5",
+            "--prefix",
+            "zz",
+        ])
+        .0
+    );
+}
+
+#[test]
+fn json_format_should_print_parseable_result() {
+    let deployer = "De2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343B5";
+    for args in [
+        ["calc", "--deployer", deployer, "--salt", "nacl"],
+        ["mine", "--deployer", deployer, "--prefix", "ab"],
+    ] {
+        let (ok, stdout) = run(&[&args[..], &["--format", "json"]].concat());
+        assert!(ok);
+        let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+        let address = json["address"].as_str().unwrap();
+        let salt = json["salt"].as_str().unwrap();
+        assert!(json["salt_hash"].as_str().unwrap().starts_with("0x"));
+        let (_, calc) = run(&["calc", "--deployer", deployer, "--salt", salt]);
+        assert_eq!(field(&calc, "address").to_lowercase(), address);
+    }
+}
+
+#[test]
+fn json_format_should_apply_to_salt_prefix_flow() {
+    let deployer = "5e17b14ADd6c386305A32928F985b29bbA34Eff5";
+    let stdin = format!("3\n{}\nv2_\na\n", deployer);
+    let (ok, stdout) =
+        run_interactive(&std::env::temp_dir(), &["--format", "json"], &stdin);
+    assert!(ok);
+    // the json line follows the prompts, which end without a newline.
+    let line = stdout.lines().last().unwrap();
+    let json: serde_json::Value =
+        serde_json::from_str(&line[line.find('{').unwrap()..]).unwrap();
+    let address = json["address"].as_str().unwrap();
+    assert!(address.starts_with("0xa"));
+    let salt = json["salt"].as_str().unwrap();
+    assert!(salt.starts_with("v2_"));
+    assert!(json["salt_hash"].as_str().unwrap().starts_with("0x"));
+    let (_, calc) = run(&["calc", "--deployer", deployer, "--salt", salt]);
+    assert_eq!(field(&calc, "address").to_lowercase(), address);
+}
+
+#[test]
+fn batch_should_append_results_to_out_file() {
+    let dir = std::env::temp_dir()
+        .join(format!("create3-cli-out-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let batch = "4\n5e17b14ADd6c386305A32928F985b29bbA34Eff5\na\n3\n";
+
+    assert!(run_interactive(&dir, &["--out", "results.csv"], batch).0);
+    let csv = fs::read_to_string(dir.join("results.csv")).unwrap();
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[0], "address,salt,salt_hash");
+    for row in &rows[1..] {
+        assert!(row.starts_with("0xa"));
+        assert_eq!(row.split(',').count(), 3);
+    }
+
+    assert!(run_interactive(&dir, &["--out", "results.json"], batch).0);
+    let json = fs::read_to_string(dir.join("results.json")).unwrap();
+    assert_eq!(json.lines().count(), 3);
+    for line in json.lines() {
+        let row: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(row["address"].as_str().unwrap().starts_with("0xa"));
+    }
+
+    assert!(!run_interactive(&dir, &["--out", "results.txt"], batch).0);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn ctrl_c_should_stop_mining_cleanly() {
+    use std::thread;
+    use std::time::Duration;
+
+    let child = Command::new(env!("CARGO_BIN_EXE_create3"))
+        .args([
+            "mine",
+            "--deployer",
+            "De2E5D408865Ec68a49AE5c0AdDa7f0Ebf0343B5",
+            "--prefix",
+            "ffffffffffffffff",
+            "--threads",
+            "1",
+        ])
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    thread::sleep(Duration::from_millis(500));
+    let killed = Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(killed.success());
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(130));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("stopping..."));
+    assert!(stderr.contains("stopped before a match was found."));
+}
@@ -0,0 +1,58 @@
+//! address derivation that must keep working without the `std` feature.
+//!
+//! run with `cargo test --no-default-features --test no_std` to check the
+//! `no_std` build.
+
+use create3::{
+    calc_addr, calc_addr_with_bytes, checksum::to_checksum_address,
+    errors::Create3GenerateSaltError, factory::calc_addr_with_proxy_hash,
+    verify_salt, Create3,
+};
+use sha3::{Digest, Keccak256};
+
+#[test]
+fn core_functions_should_derive_addresses() {
+    let deployer: [u8; 20] =
+        hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let salt: [u8; 32] = hex::decode(
+        "3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb",
+    )
+    .unwrap()
+    .try_into()
+    .unwrap();
+    let addr = calc_addr_with_bytes(&deployer, &salt);
+    assert_eq!(
+        to_checksum_address(&addr),
+        "0x442188F25da4ac213D55aE81F1BFB421a4eb4562"
+    );
+    assert_eq!(Create3::new(&deployer).calc_addr_with_bytes(&salt), addr);
+    let proxy_hash: [u8; 32] = Keccak256::digest(
+        hex::decode("67363d3d37363d34f03d5260086018f3").unwrap(),
+    )
+    .into();
+    assert_eq!(
+        calc_addr_with_proxy_hash(&deployer, &salt, &proxy_hash),
+        addr
+    );
+    assert_eq!(
+        Create3::new(&deployer).calc_addr(b"nacl"),
+        calc_addr(&deployer, b"nacl")
+    );
+}
+
+#[test]
+fn core_functions_should_verify_salts() {
+    let deployer: Vec<u8> =
+        hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8").unwrap();
+    let addr = calc_addr(&deployer, b"nacl");
+    let prefix = &hex::encode(addr)[..3];
+    assert!(verify_salt(&deployer, b"nacl", prefix));
+    assert!(!verify_salt(&deployer, b"nacl", "xyz"));
+    assert_eq!(
+        Create3GenerateSaltError::PrefixTooLong.to_string(),
+        "prefix too long (max 20 bytes)."
+    );
+}